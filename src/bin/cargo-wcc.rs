@@ -3,7 +3,7 @@ mod cli;
 
 use clap::{Parser, Subcommand};
 
-use cli::{run_weighted_code_coverage, CargoArgs};
+use cli::{run_history, run_weighted_code_coverage, CargoArgs, Command};
 
 #[derive(Subcommand)]
 enum Cmd {
@@ -21,23 +21,35 @@ struct Cli {
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let Cli {
-        cargo_args: Cmd::Wcc(mut cargo_args),
+        cargo_args: Cmd::Wcc(cargo_args),
     } = Cli::parse();
 
+    let mut args = match cargo_args {
+        CargoArgs {
+            command: Some(Command::History(history_args)),
+            ..
+        } => return Ok(run_history(history_args)?),
+        CargoArgs {
+            args: Some(args), ..
+        } => args,
+        // Clap requires the analysis arguments when there is no subcommand.
+        CargoArgs { .. } => unreachable!(),
+    };
+
     let mut cmd = cargo_metadata::MetadataCommand::new();
     if let Some(ref manifest_path) = cargo_args.manifest_path {
         cmd.manifest_path(manifest_path);
     }
 
     let metadata = cmd.exec()?;
-    cargo_args.args.project_path = metadata.workspace_packages()[0]
+    args.project_path = metadata.workspace_packages()[0]
         .manifest_path
         .parent()
         .unwrap()
         .join("src")
         .into_std_path_buf();
 
-    run_weighted_code_coverage(cargo_args.args);
+    run_weighted_code_coverage(args);
 
     Ok(())
 }
@@ -3,9 +3,29 @@ mod cli;
 
 use clap::Parser;
 
-use cli::{run_weighted_code_coverage, Args};
+use cli::{run_history, run_weighted_code_coverage, Args, Command};
 
-fn main() {
-    let args = Args::parse();
-    run_weighted_code_coverage(args);
+#[derive(Parser)]
+#[clap(author, version, about, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Option<Command>,
+    #[clap(flatten)]
+    args: Option<Args>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    match Cli::parse() {
+        Cli {
+            command: Some(Command::History(history_args)),
+            ..
+        } => run_history(history_args)?,
+        Cli {
+            args: Some(args), ..
+        } => run_weighted_code_coverage(args),
+        // Clap requires the analysis arguments when there is no subcommand.
+        Cli { .. } => unreachable!(),
+    }
+
+    Ok(())
 }
@@ -1,10 +1,13 @@
 use std::path::PathBuf;
 
 use clap::builder::{PossibleValuesParser, TypedValueParser};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use tracing_subscriber::EnvFilter;
 
-use weighted_code_coverage::{GrcovFile, GrcovFormat, Mode, Sort, Thresholds, WccRunner};
+use weighted_code_coverage::{
+    Complexity, Error, GrcovFile, GrcovFormat, History, Mode, MutationsFile, MutationsFormat,
+    Output, SmellsFile, SmellsFormat, Sort, Thresholds, WccRunner,
+};
 
 #[inline]
 fn thresholds_long_help() -> &'static str {
@@ -12,19 +15,43 @@ fn thresholds_long_help() -> &'static str {
 The input string must therefore follow the same order: `-t '60.0,10.0,10.0'`.
 Wcc is a percentage value, so its value should be in the [0, 100] range.
 The complexities should tipically be in the [0, 15] range,
-assuming that a code space with a complexity higher than 15 is too complex."
+assuming that a code space with a complexity higher than 15 is too complex.
+The thresholds of the additional complexities can follow as `name=value` pairs: `-t '60.0,10.0,10.0,args=6,exits=5'`.
+Their default values are: halstead-effort=10000, halstead-difficulty=20, maintainability-index=80, exits=4, args=5."
 }
 
 const JSON_OUTPUT_PATH: &str = "./wcc.json";
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
 pub(crate) struct CargoArgs {
     /// Path to a Cargo.toml.
     #[clap(long)]
     pub(crate) manifest_path: Option<PathBuf>,
+    #[clap(subcommand)]
+    pub(crate) command: Option<Command>,
     #[clap(flatten)]
-    pub(crate) args: Args,
+    pub(crate) args: Option<Args>,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum Command {
+    /// List and compare the runs stored in a history file.
+    History(HistoryArgs),
+}
+
+#[derive(Parser, Debug)]
+pub(crate) struct HistoryArgs {
+    /// Path of the history file.
+    #[clap(long, required = true, value_hint = clap::ValueHint::FilePath)]
+    history: PathBuf,
+    /// Compare two runs, selected by their number in the list, commit hash prefix or label.
+    #[clap(long, num_args = 2, value_names = ["BASE", "HEAD"])]
+    compare: Option<Vec<String>>,
+    /// Complexity metric of the listed and compared metrics.
+    #[clap(long, default_value_t = Complexity::Cyclomatic, value_parser = PossibleValuesParser::new(Complexity::all())
+        .map(|s| s.parse::<Complexity>().unwrap()))]
+    complexity: Complexity,
 }
 
 #[derive(Parser, Debug)]
@@ -54,12 +81,93 @@ pub(crate) struct Args {
     #[clap(long, short = 's', default_value_t = Sort::Wcc, value_parser = PossibleValuesParser::new(Sort::all())
         .map(|s| s.parse::<Sort>().unwrap()))]
     sort: Sort,
+    /// Report printed on the standard output.
+    #[clap(long, short = 'o', default_value_t = Output::None, value_parser = PossibleValuesParser::new(Output::all())
+        .map(|s| s.parse::<Output>().unwrap()))]
+    output: Output,
+    /// Additional complexity metrics against which the metrics are computed.
+    #[clap(long, value_delimiter = ',', value_parser = PossibleValuesParser::new(Complexity::all())
+        .map(|s| s.parse::<Complexity>().unwrap()))]
+    complexities: Vec<Complexity>,
+    /// Format of the linter diagnostics file used as code smells by Skunk.
+    #[clap(long, requires = "smells_path", value_parser = PossibleValuesParser::new(SmellsFormat::all())
+        .map(|s| s.parse::<SmellsFormat>().unwrap()))]
+    smells_format: Option<SmellsFormat>,
+    /// Path of the linter diagnostics file.
+    #[clap(long, requires = "smells_format", value_hint = clap::ValueHint::FilePath)]
+    smells_path: Option<PathBuf>,
+    /// Format of the mutation testing results used to adjust coverage.
+    #[clap(long, requires = "mutations_path", value_parser = PossibleValuesParser::new(MutationsFormat::all())
+        .map(|s| s.parse::<MutationsFormat>().unwrap()))]
+    mutations_format: Option<MutationsFormat>,
+    /// Path of the mutation testing results file.
+    #[clap(long, requires = "mutations_format", value_hint = clap::ValueHint::FilePath)]
+    mutations_path: Option<PathBuf>,
+    /// Compute the metrics weighting coverage by the number of hits of each line.
+    #[clap(long)]
+    hit_weighted: bool,
     /// Path of the json output.
     #[clap(long, default_value = JSON_OUTPUT_PATH, value_hint = clap::ValueHint::FilePath)]
     json: PathBuf,
     /// Path of the html output.
     #[clap(long, value_hint = clap::ValueHint::DirPath)]
     html: Option<PathBuf>,
+    /// Directory of templates overriding the built-in html templates with the same name.
+    #[clap(long, requires = "html", value_hint = clap::ValueHint::DirPath)]
+    html_templates: Option<PathBuf>,
+    /// Write the whole html output into a single page, which is easier to share.
+    #[clap(long, requires = "html")]
+    html_single: bool,
+    /// Write vendored copies of the html output assets, so that it can be viewed offline.
+    #[cfg(feature = "offline-html")]
+    #[clap(long, requires = "html")]
+    html_offline: bool,
+    /// Path of the CSV output.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    csv: Option<PathBuf>,
+    /// Path of the TSV output.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    tsv: Option<PathBuf>,
+    /// Path of the JUnit XML report.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    junit: Option<PathBuf>,
+    /// Path of the Markdown summary.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    markdown: Option<PathBuf>,
+    /// Number of riskiest files or functions listed in the Markdown summary.
+    #[clap(long, default_value_t = 10)]
+    markdown_top: usize,
+    /// Maximum size in bytes of the Markdown summary, e.g. 65536 for GitHub comments.
+    #[clap(long)]
+    markdown_max_size: Option<usize>,
+    /// Number of functions listed by the `priorities` report.
+    #[clap(long, default_value_t = 10)]
+    priorities_top: usize,
+    /// Path of the SARIF output.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    sarif: Option<PathBuf>,
+    /// Path of the Code Climate issues, also used by GitLab code quality reports.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    code_climate: Option<PathBuf>,
+    /// Path of the SonarQube generic external issues.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    sonar_issues: Option<PathBuf>,
+    /// Path of the SonarQube generic test coverage.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    sonar_coverage: Option<PathBuf>,
+    /// Write the Wcc and CRAP SVG badges computed with the chosen complexity metric.
+    #[clap(long, value_parser = PossibleValuesParser::new(["cyclomatic", "cognitive"])
+        .map(|s| s.parse::<Complexity>().unwrap()))]
+    badges: Option<Complexity>,
+    /// Path of the history file, to which the project and files metrics of the run are appended.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    history: Option<PathBuf>,
+    /// Commit hash of the analyzed code, stored in the history.
+    #[clap(long, requires = "history")]
+    commit: Option<String>,
+    /// Label of the run, stored in the history.
+    #[clap(long, requires = "history")]
+    label: Option<String>,
     #[clap(long, short = 'v', global = true)]
     verbose: bool,
 }
@@ -89,12 +197,101 @@ pub(crate) fn run_weighted_code_coverage(args: Args) {
         .n_threads(args.threads)
         .mode(args.mode)
         .sort_by(args.sort)
+        .output(args.output)
+        .priorities_top(args.priorities_top)
+        .hit_weighted(args.hit_weighted)
+        .complexities(&args.complexities)
         .json_path(&args.json);
 
     // If present, set the path of the html output directory.
     if let Some(html_path) = &args.html {
         wcc_runner = wcc_runner.html_path(html_path);
     }
+    if let Some(html_templates) = &args.html_templates {
+        wcc_runner = wcc_runner.html_templates(html_templates);
+    }
+    wcc_runner = wcc_runner.html_single(args.html_single);
+    #[cfg(feature = "offline-html")]
+    {
+        wcc_runner = wcc_runner.html_offline(args.html_offline);
+    }
+
+    // If present, set the linter diagnostics file.
+    if let (Some(smells_format), Some(smells_path)) = (&args.smells_format, &args.smells_path) {
+        let smells_file = match smells_format {
+            SmellsFormat::Cargo => SmellsFile::Cargo(smells_path.as_path()),
+            SmellsFormat::Sarif => SmellsFile::Sarif(smells_path.as_path()),
+        };
+        wcc_runner = wcc_runner.smells_file(smells_file);
+    }
+
+    // If present, set the mutation testing results file.
+    if let (Some(mutations_format), Some(mutations_path)) =
+        (&args.mutations_format, &args.mutations_path)
+    {
+        let mutations_file = match mutations_format {
+            MutationsFormat::CargoMutants => MutationsFile::CargoMutants(mutations_path.as_path()),
+            MutationsFormat::Stryker => MutationsFile::Stryker(mutations_path.as_path()),
+        };
+        wcc_runner = wcc_runner.mutations_file(mutations_file);
+    }
+
+    // If present, set the paths of the CSV and TSV outputs.
+    if let Some(csv_path) = &args.csv {
+        wcc_runner = wcc_runner.csv_path(csv_path);
+    }
+    if let Some(tsv_path) = &args.tsv {
+        wcc_runner = wcc_runner.tsv_path(tsv_path);
+    }
+
+    // If present, set the path of the JUnit XML report.
+    if let Some(junit_path) = &args.junit {
+        wcc_runner = wcc_runner.junit_path(junit_path);
+    }
+
+    // If present, set the path and the size limit of the Markdown summary.
+    if let Some(markdown_path) = &args.markdown {
+        wcc_runner = wcc_runner
+            .markdown_path(markdown_path)
+            .markdown_top(args.markdown_top);
+    }
+    if let Some(markdown_max_size) = args.markdown_max_size {
+        wcc_runner = wcc_runner.markdown_max_size(markdown_max_size);
+    }
+
+    // If present, set the path of the SARIF output.
+    if let Some(sarif_path) = &args.sarif {
+        wcc_runner = wcc_runner.sarif_path(sarif_path);
+    }
+
+    // If present, set the path of the Code Climate issues.
+    if let Some(code_climate_path) = &args.code_climate {
+        wcc_runner = wcc_runner.code_climate_path(code_climate_path);
+    }
+
+    // If present, set the paths of the SonarQube issues and coverage.
+    if let Some(sonar_issues_path) = &args.sonar_issues {
+        wcc_runner = wcc_runner.sonar_issues_path(sonar_issues_path);
+    }
+    if let Some(sonar_coverage_path) = &args.sonar_coverage {
+        wcc_runner = wcc_runner.sonar_coverage_path(sonar_coverage_path);
+    }
+
+    // If present, write the badges with the chosen complexity metric.
+    if let Some(complexity) = args.badges {
+        wcc_runner = wcc_runner.badges(complexity);
+    }
+
+    // If present, append the run to the history.
+    if let Some(history_path) = &args.history {
+        wcc_runner = wcc_runner.history_path(history_path);
+    }
+    if let Some(commit) = &args.commit {
+        wcc_runner = wcc_runner.history_commit(commit);
+    }
+    if let Some(label) = &args.label {
+        wcc_runner = wcc_runner.history_label(label);
+    }
 
     // Define the grcov file.
     let grcov_file = match args.grcov_format {
@@ -105,3 +302,22 @@ pub(crate) fn run_weighted_code_coverage(args: Args) {
     // Run WccRunner.
     wcc_runner.run(&args.project_path, grcov_file).unwrap();
 }
+
+pub(crate) fn run_history(args: HistoryArgs) -> Result<(), Error> {
+    let history = History::from_path(&args.history)?;
+
+    let output = match args.compare.as_deref() {
+        Some([base, head]) => {
+            let find = |key: &str| {
+                history
+                    .find(key)
+                    .ok_or_else(|| Error::History(format!("No run {key:?} in the history")))
+            };
+            History::format_comparison(find(base)?, find(head)?, args.complexity)?
+        }
+        _ => history.format_runs(args.complexity),
+    };
+    print!("{output}");
+
+    Ok(())
+}
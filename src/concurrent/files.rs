@@ -1,11 +1,13 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-use crate::metrics::MetricsThresholds;
+use crate::metrics::MetricsConfig;
 
-use super::{functions::FunctionMetrics, Metrics, ProjectData};
+use super::{functions::FunctionMetrics, targets::Targets, Metrics, ProjectData};
 
 /// File metrics.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct FileMetrics {
     /// File name.
     pub name: String,
@@ -14,6 +16,12 @@ pub struct FileMetrics {
     /// File functions.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub functions: Option<Vec<FunctionMetrics>>,
+    /// Number of instrumented lines.
+    #[serde(default)]
+    pub instrumented_lines: usize,
+    /// Coverage needed to pass the thresholds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub targets: Option<Targets>,
 }
 
 impl FileMetrics {
@@ -21,13 +29,15 @@ impl FileMetrics {
     pub(crate) fn new(
         name: String,
         project_data: ProjectData,
-        metrics_thresholds: MetricsThresholds,
+        metrics_config: MetricsConfig,
         functions: Option<Vec<FunctionMetrics>>,
     ) -> Self {
         Self {
             name,
-            metrics: Metrics::file(project_data, metrics_thresholds),
+            metrics: Metrics::file(project_data, metrics_config),
             functions,
+            instrumented_lines: project_data.ploc as usize,
+            targets: Some(Targets::file(project_data, metrics_config.thresholds)),
         }
     }
 }
@@ -1,16 +1,38 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-use crate::metrics::MetricsThresholds;
+use crate::{metrics::MetricsConfig, smells::Smell};
 
-use super::{Metrics, SpaceData};
+use super::{targets::Targets, LineRange, Metrics, SpaceData};
 
 /// Function metrics.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct FunctionMetrics {
     /// Function name.
     pub name: String,
+    /// First line of the function.
+    pub start_line: usize,
+    /// Last line of the function.
+    pub end_line: usize,
     /// Function metrics.
     pub metrics: Metrics,
+    /// Code smells attributed to the function.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub smells: Vec<Smell>,
+    /// Number of instrumented lines.
+    #[serde(default)]
+    pub instrumented_lines: usize,
+    // Number of covered lines.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub(crate) covered_lines: usize,
+    /// Ranges of the uncovered lines.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uncovered_lines: Vec<LineRange>,
+    /// Coverage needed to pass the thresholds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub targets: Option<Targets>,
 }
 
 impl FunctionMetrics {
@@ -18,11 +40,20 @@ impl FunctionMetrics {
     pub(crate) fn new(
         name: String,
         space_data: SpaceData,
-        metrics_thresholds: MetricsThresholds,
+        smells: Vec<Smell>,
+        uncovered_lines: Vec<LineRange>,
+        metrics_config: MetricsConfig,
     ) -> Self {
         Self {
             name,
-            metrics: Metrics::function(space_data, metrics_thresholds),
+            start_line: space_data.start_line,
+            end_line: space_data.end_line,
+            metrics: Metrics::function(space_data, metrics_config),
+            smells,
+            instrumented_lines: space_data.ploc as usize,
+            covered_lines: space_data.covered_lines as usize,
+            uncovered_lines,
+            targets: Some(Targets::function(space_data, metrics_config.thresholds)),
         }
     }
 }
@@ -1,8 +1,9 @@
 pub(crate) mod files;
 pub(crate) mod functions;
+pub(crate) mod targets;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Mutex,
 };
@@ -10,18 +11,19 @@ use std::{
 use crossbeam::channel::{Receiver, Sender};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use rust_code_analysis::{FuncSpace, SpaceKind};
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{Error, Result},
     grcov::{covdir::Covdir, coveralls::Coveralls},
     metrics::{
-        crap::crap,
-        get_line_space, get_root, get_space_name, round_sd,
-        skunk::skunk,
-        wcc::{wcc, wcc_function, WCC_COMPLEXITY_THRESHOLD},
-        MetricsThresholds,
+        get_complexities, get_enclosing_spaces, get_line_space, get_root, get_space_name,
+        hits::hit_weight, round_sd, wcc::wcc_complexity_threshold, Complexities, Metric,
+        MetricInput, MetricsConfig,
     },
+    mutations::Mutations,
+    smells::{Smell, Smells},
     Complexity, Mode, Sort,
 };
 
@@ -98,14 +100,18 @@ pub(crate) enum Grcov {
 
 impl Grcov {
     #[inline]
-    fn get_lines_coverage(&self, file: &Path) -> Option<&Vec<Option<i32>>> {
+    pub(crate) fn get_lines_coverage(&self, file: &Path) -> Option<&Vec<Option<i32>>> {
         match self {
             Grcov::Coveralls(coveralls) => coveralls.0.get(file).map(|c| &c.coverage),
             Grcov::Covdir(covdir) => covdir.source_files.get(file).map(|c| &c.coverage),
         }
     }
 
-    fn get_file_name<'a>(&'a self, file: &'a Path, project_path: &Path) -> Option<&str> {
+    pub(crate) fn get_file_name<'a>(
+        &'a self,
+        file: &'a Path,
+        project_path: &Path,
+    ) -> Option<&'a str> {
         match self {
             Grcov::Coveralls(coveralls) => coveralls.0.get(file)?.name.to_str(),
             Grcov::Covdir(_) => file.strip_prefix(project_path).ok()?.to_str(),
@@ -113,8 +119,31 @@ impl Grcov {
     }
 }
 
+/// Value of a custom metric.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricValue {
+    /// Metric name.
+    pub name: String,
+    /// Metric score.
+    pub score: f64,
+    /// Indicates whether the score crosses the metric threshold.
+    pub exceeds_threshold: bool,
+}
+
+impl MetricValue {
+    #[inline]
+    fn new(metric: &dyn Metric, score: f64, complexity: Complexity) -> Self {
+        Self {
+            name: metric.name().to_owned(),
+            score,
+            exceeds_threshold: metric.exceeds_threshold(score, complexity),
+        }
+    }
+}
+
 /// Metrics data.
-#[derive(Debug, Serialize, Clone, Copy, Default)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct MetricsData {
     /// Wcc.
@@ -127,179 +156,283 @@ pub struct MetricsData {
     pub complexity: f64,
     /// Inidcates whether one of the metrics exceeds the threshold.
     pub is_complex: bool,
+    /// Wcc computed on hit-weighted coverage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_wcc: Option<f64>,
+    /// CRAP computed on hit-weighted coverage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_crap: Option<f64>,
+    /// Skunk computed on hit-weighted coverage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_skunk: Option<f64>,
+    /// Wcc computed on mutation-adjusted coverage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mutation_wcc: Option<f64>,
+    /// CRAP computed on mutation-adjusted coverage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mutation_crap: Option<f64>,
+    /// Custom metrics, in the same order in which they have been registered.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom: Vec<MetricValue>,
 }
 
 impl MetricsData {
+    fn new(input: MetricInput, metrics_config: MetricsConfig) -> Self {
+        let mut metrics_data = Self {
+            wcc: metrics_config.wcc().score(&input),
+            crap: metrics_config.crap().score(&input),
+            skunk: metrics_config.skunk().score(&input),
+            complexity: input.complexity,
+            custom: metrics_config
+                .custom_metrics
+                .iter()
+                .map(|metric| {
+                    MetricValue::new(metric.as_ref(), metric.score(&input), input.complexity_type)
+                })
+                .collect(),
+            ..Self::default()
+        };
+
+        if metrics_config.hit_weighted {
+            let hit_input = input.hit_weighted();
+            metrics_data.hit_wcc = Some(metrics_config.wcc().score(&hit_input));
+            metrics_data.hit_crap = Some(metrics_config.crap().score(&hit_input));
+            metrics_data.hit_skunk = Some(metrics_config.skunk().score(&hit_input));
+        }
+
+        if metrics_config.mutation_adjusted {
+            let mutation_input = input.mutation_adjusted();
+            metrics_data.mutation_wcc = Some(metrics_config.wcc().score(&mutation_input));
+            metrics_data.mutation_crap = Some(metrics_config.crap().score(&mutation_input));
+        }
+
+        metrics_data.update_is_complex(metrics_config, input.complexity_type)
+    }
+
+    #[inline]
     fn file(
         project_data: ProjectData,
-        metrics_thresholds: MetricsThresholds,
+        metrics_config: MetricsConfig,
         complexity_type: Complexity,
     ) -> Self {
-        let coverage = project_data.covered_lines / project_data.ploc;
-        let (complexity, wcc_coverage) = match complexity_type {
-            Complexity::Cyclomatic => (
-                project_data.cyclomatic_complexity / project_data.num_spaces,
-                project_data.wcc_cyclomatic_coverage,
-            ),
-            Complexity::Cognitive => (
-                project_data.cognitive_complexity / project_data.num_spaces,
-                project_data.wcc_cognitive_coverage,
-            ),
-        };
+        let mut metrics_data = Self::new(project_data.input(complexity_type), metrics_config);
+        metrics_data.complexity = round_sd(metrics_data.complexity);
 
-        let wcc = wcc(wcc_coverage, project_data.ploc);
-        let crap = crap(coverage, complexity);
-        let skunk = skunk(coverage, complexity);
-
-        Self {
-            wcc,
-            crap,
-            skunk,
-            complexity: round_sd(complexity),
-            is_complex: metrics_thresholds.is_complex(wcc, crap, skunk, complexity_type),
-        }
+        metrics_data
     }
 
+    #[inline]
     fn function(
         space_data: SpaceData,
-        metrics_thresholds: MetricsThresholds,
+        metrics_config: MetricsConfig,
         complexity_type: Complexity,
     ) -> Self {
-        let coverage = space_data.covered_lines / space_data.ploc;
-        let complexity = match complexity_type {
-            Complexity::Cyclomatic => space_data.cyclomatic_complexity,
-            Complexity::Cognitive => space_data.cognitive_complexity,
-        };
-
-        let wcc = wcc_function(complexity, space_data.covered_lines, space_data.ploc);
-        let crap = crap(coverage, complexity);
-        let skunk = skunk(coverage, complexity);
+        let mut metrics_data = Self::new(space_data.input(complexity_type), metrics_config);
+        metrics_data.complexity = round_sd(metrics_data.complexity);
 
-        Self {
-            wcc,
-            crap,
-            skunk,
-            complexity,
-            is_complex: metrics_thresholds.is_complex(wcc, crap, skunk, complexity_type),
-        }
+        metrics_data
     }
 
+    #[inline]
     fn project_total(
         project_data: ProjectData,
-        metrics_thresholds: MetricsThresholds,
+        metrics_config: MetricsConfig,
         complexity_type: Complexity,
     ) -> Self {
-        let coverage = project_data.covered_lines / project_data.ploc;
-        let (complexity, wcc_coverage) = match complexity_type {
-            Complexity::Cyclomatic => (
-                project_data.cyclomatic_complexity / project_data.num_spaces,
-                project_data.wcc_cyclomatic_coverage,
-            ),
-            Complexity::Cognitive => (
-                project_data.cognitive_complexity / project_data.num_spaces,
-                project_data.wcc_cognitive_coverage,
-            ),
-        };
+        Self::new(project_data.input(complexity_type), metrics_config)
+    }
 
-        let wcc = round_sd((wcc_coverage / project_data.ploc) * 100.0);
-        let crap = crap(coverage, complexity);
-        let skunk = skunk(coverage, complexity);
+    // Updates the complex flags of the metrics according to their current values.
+    fn update_is_complex(mut self, metrics_config: MetricsConfig, complexity: Complexity) -> Self {
+        self.custom
+            .iter_mut()
+            .zip(metrics_config.custom_metrics)
+            .for_each(|(value, metric)| {
+                value.exceeds_threshold = metric.exceeds_threshold(value.score, complexity)
+            });
+        self.is_complex = metrics_config.wcc().exceeds_threshold(self.wcc, complexity)
+            || metrics_config
+                .crap()
+                .exceeds_threshold(self.crap, complexity)
+            || metrics_config
+                .skunk()
+                .exceeds_threshold(self.skunk, complexity)
+            || self.custom.iter().any(|value| value.exceeds_threshold);
 
-        Self {
-            wcc,
-            crap,
-            skunk,
-            complexity,
-            is_complex: metrics_thresholds.is_complex(wcc, crap, skunk, complexity_type),
-        }
+        self
+    }
+
+    #[inline]
+    fn custom_score(&self, name: &str) -> f64 {
+        self.custom
+            .iter()
+            .find(|value| value.name == name)
+            .map_or(0.0, |value| value.score)
     }
 
-    const fn project_min() -> Self {
+    fn project_min(metrics_config: MetricsConfig) -> Self {
         Self {
             wcc: f64::MAX,
             crap: f64::MAX,
             skunk: f64::MAX,
             complexity: f64::MAX,
             is_complex: false,
+            hit_wcc: None,
+            hit_crap: None,
+            hit_skunk: None,
+            mutation_wcc: None,
+            mutation_crap: None,
+            custom: custom_values(metrics_config, f64::MAX),
         }
     }
 
     #[inline]
     fn update_project_min(
         mut self,
-        other: MetricsData,
-        metrics_thresholds: MetricsThresholds,
+        other: &MetricsData,
+        metrics_config: MetricsConfig,
         complexity: Complexity,
     ) -> Self {
         self.wcc = self.wcc.min(other.wcc);
         self.crap = self.crap.min(other.crap);
         self.skunk = self.skunk.min(other.skunk);
         self.complexity = self.complexity.min(other.complexity);
-        self.is_complex =
-            metrics_thresholds.is_complex(self.wcc, self.crap, self.skunk, complexity);
-
-        self
+        self.hit_wcc = combine(self.hit_wcc, other.hit_wcc, f64::min);
+        self.hit_crap = combine(self.hit_crap, other.hit_crap, f64::min);
+        self.hit_skunk = combine(self.hit_skunk, other.hit_skunk, f64::min);
+        self.mutation_wcc = combine(self.mutation_wcc, other.mutation_wcc, f64::min);
+        self.mutation_crap = combine(self.mutation_crap, other.mutation_crap, f64::min);
+        combine_custom(&mut self.custom, &other.custom, f64::min);
+
+        self.update_is_complex(metrics_config, complexity)
     }
 
-    const fn project_max() -> Self {
+    fn project_max(metrics_config: MetricsConfig) -> Self {
         Self {
             wcc: f64::MIN,
             crap: f64::MIN,
             skunk: f64::MIN,
             complexity: f64::MIN,
             is_complex: false,
+            hit_wcc: None,
+            hit_crap: None,
+            hit_skunk: None,
+            mutation_wcc: None,
+            mutation_crap: None,
+            custom: custom_values(metrics_config, f64::MIN),
         }
     }
 
     #[inline]
     fn update_project_max(
         mut self,
-        other: MetricsData,
-        metrics_thresholds: MetricsThresholds,
+        other: &MetricsData,
+        metrics_config: MetricsConfig,
         complexity: Complexity,
     ) -> Self {
         self.wcc = self.wcc.max(other.wcc);
         self.crap = self.crap.max(other.crap);
         self.skunk = self.skunk.max(other.skunk);
         self.complexity = self.complexity.max(other.complexity);
-        self.is_complex =
-            metrics_thresholds.is_complex(self.wcc, self.crap, self.skunk, complexity);
+        self.hit_wcc = combine(self.hit_wcc, other.hit_wcc, f64::max);
+        self.hit_crap = combine(self.hit_crap, other.hit_crap, f64::max);
+        self.hit_skunk = combine(self.hit_skunk, other.hit_skunk, f64::max);
+        self.mutation_wcc = combine(self.mutation_wcc, other.mutation_wcc, f64::max);
+        self.mutation_crap = combine(self.mutation_crap, other.mutation_crap, f64::max);
+        combine_custom(&mut self.custom, &other.custom, f64::max);
+
+        self.update_is_complex(metrics_config, complexity)
+    }
 
-        self
+    fn project_sum(metrics_config: MetricsConfig) -> Self {
+        Self {
+            custom: custom_values(metrics_config, 0.0),
+            ..Self::default()
+        }
     }
 
     #[inline]
-    fn sum(mut self, other: MetricsData) -> Self {
+    fn sum(mut self, other: &MetricsData) -> Self {
         self.wcc += other.wcc;
         self.crap += other.crap;
         self.skunk += other.skunk;
         self.complexity += other.complexity;
+        self.hit_wcc = combine(self.hit_wcc, other.hit_wcc, |a, b| a + b);
+        self.hit_crap = combine(self.hit_crap, other.hit_crap, |a, b| a + b);
+        self.hit_skunk = combine(self.hit_skunk, other.hit_skunk, |a, b| a + b);
+        self.mutation_wcc = combine(self.mutation_wcc, other.mutation_wcc, |a, b| a + b);
+        self.mutation_crap = combine(self.mutation_crap, other.mutation_crap, |a, b| a + b);
+        combine_custom(&mut self.custom, &other.custom, |a, b| a + b);
 
         self
     }
 
     fn project_average(
-        self,
+        mut self,
         num_files: f64,
-        metrics_thresholds: MetricsThresholds,
+        metrics_config: MetricsConfig,
         complexity: Complexity,
     ) -> Self {
-        let wcc = round_sd(self.wcc / num_files);
-        let crap = round_sd(self.crap / num_files);
-        let skunk = round_sd(self.skunk / num_files);
+        self.wcc = round_sd(self.wcc / num_files);
+        self.crap = round_sd(self.crap / num_files);
+        self.skunk = round_sd(self.skunk / num_files);
+        self.complexity = round_sd(self.complexity / num_files);
+        self.hit_wcc = self.hit_wcc.map(|hit_wcc| round_sd(hit_wcc / num_files));
+        self.hit_crap = self.hit_crap.map(|hit_crap| round_sd(hit_crap / num_files));
+        self.hit_skunk = self
+            .hit_skunk
+            .map(|hit_skunk| round_sd(hit_skunk / num_files));
+        self.mutation_wcc = self
+            .mutation_wcc
+            .map(|mutation_wcc| round_sd(mutation_wcc / num_files));
+        self.mutation_crap = self
+            .mutation_crap
+            .map(|mutation_crap| round_sd(mutation_crap / num_files));
+        self.custom
+            .iter_mut()
+            .for_each(|value| value.score = round_sd(value.score / num_files));
+
+        self.update_is_complex(metrics_config, complexity)
+    }
+}
 
-        Self {
-            wcc,
-            crap,
-            skunk,
-            complexity: round_sd(self.complexity / num_files),
-            is_complex: metrics_thresholds.is_complex(wcc, crap, skunk, complexity),
-        }
+// Combines two optional metric values, keeping the available one
+// when the other is missing.
+#[inline]
+fn combine(a: Option<f64>, b: Option<f64>, f: impl Fn(f64, f64) -> f64) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(f(a, b)),
+        (a, b) => a.or(b),
     }
 }
 
+// Combines the scores of two lists of custom metrics values,
+// which are always in the same order.
+#[inline]
+fn combine_custom(values: &mut [MetricValue], others: &[MetricValue], f: impl Fn(f64, f64) -> f64) {
+    values
+        .iter_mut()
+        .zip(others)
+        .for_each(|(value, other)| value.score = f(value.score, other.score));
+}
+
+// Initializes the values of the custom metrics with the same score.
+#[inline]
+fn custom_values(metrics_config: MetricsConfig, score: f64) -> Vec<MetricValue> {
+    metrics_config
+        .custom_metrics
+        .iter()
+        .map(|metric| MetricValue {
+            name: metric.name().to_owned(),
+            score,
+            exceeds_threshold: false,
+        })
+        .collect()
+}
+
 /// Metrics.
-#[derive(Debug, Serialize, Clone, Copy, Default)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct Metrics {
     /// Cyclomatic.
     pub cyclomatic: MetricsData,
@@ -307,119 +440,248 @@ pub struct Metrics {
     pub cognitive: MetricsData,
     /// Coverage.
     pub coverage: f64,
+    /// Coverage weighted by the number of hits of each line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_coverage: Option<f64>,
+    /// Percentage of killed mutants, when the code has been mutated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mutation_score: Option<f64>,
+    /// Metrics computed against the additional complexity metrics.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub complexities: BTreeMap<Complexity, MetricsData>,
 }
 
 impl Metrics {
-    fn file(project_data: ProjectData, metrics_thresholds: MetricsThresholds) -> Self {
+    // Cyclomatic score of the metric used to sort the output.
+    pub(crate) fn sort_score(&self, sort_by: &Sort) -> f64 {
+        match sort_by {
+            Sort::Wcc => self.cyclomatic.wcc,
+            Sort::Crap => self.cyclomatic.crap,
+            Sort::Skunk => self.cyclomatic.skunk,
+            Sort::HitWcc => self.cyclomatic.hit_wcc.unwrap_or_default(),
+            Sort::HitCrap => self.cyclomatic.hit_crap.unwrap_or_default(),
+            Sort::HitSkunk => self.cyclomatic.hit_skunk.unwrap_or_default(),
+            Sort::Custom(name) => self.cyclomatic.custom_score(name),
+        }
+    }
+
+    fn file(project_data: ProjectData, metrics_config: MetricsConfig) -> Self {
         Self {
-            cyclomatic: MetricsData::file(project_data, metrics_thresholds, Complexity::Cyclomatic),
-            cognitive: MetricsData::file(project_data, metrics_thresholds, Complexity::Cognitive),
+            cyclomatic: MetricsData::file(project_data, metrics_config, Complexity::Cyclomatic),
+            cognitive: MetricsData::file(project_data, metrics_config, Complexity::Cognitive),
             coverage: round_sd((project_data.covered_lines / project_data.ploc) * 100.0),
+            hit_coverage: metrics_config
+                .hit_weighted
+                .then(|| round_sd((project_data.hit_covered_lines / project_data.ploc) * 100.0)),
+            mutation_score: project_data.mutation_score(),
+            complexities: complexities(metrics_config, |complexity| {
+                MetricsData::file(project_data, metrics_config, complexity)
+            }),
         }
     }
 
-    fn function(space_data: SpaceData, metrics_thresholds: MetricsThresholds) -> Self {
+    fn function(space_data: SpaceData, metrics_config: MetricsConfig) -> Self {
         Self {
-            cyclomatic: MetricsData::function(
-                space_data,
-                metrics_thresholds,
-                Complexity::Cyclomatic,
-            ),
-            cognitive: MetricsData::function(space_data, metrics_thresholds, Complexity::Cognitive),
+            cyclomatic: MetricsData::function(space_data, metrics_config, Complexity::Cyclomatic),
+            cognitive: MetricsData::function(space_data, metrics_config, Complexity::Cognitive),
             coverage: round_sd((space_data.covered_lines / space_data.ploc) * 100.0),
+            hit_coverage: metrics_config
+                .hit_weighted
+                .then(|| round_sd((space_data.hit_covered_lines / space_data.ploc) * 100.0)),
+            mutation_score: mutation_score(space_data.mutants, space_data.killed_mutants),
+            complexities: complexities(metrics_config, |complexity| {
+                MetricsData::function(space_data, metrics_config, complexity)
+            }),
         }
     }
 
-    fn project_total(project_data: ProjectData, metrics_thresholds: MetricsThresholds) -> Self {
+    fn project_total(project_data: ProjectData, metrics_config: MetricsConfig) -> Self {
         let coverage = round_sd((project_data.covered_lines / project_data.ploc) * 100.0);
+        let hit_coverage = metrics_config
+            .hit_weighted
+            .then(|| round_sd((project_data.hit_covered_lines / project_data.ploc) * 100.0));
+        let mutation_score = project_data.mutation_score();
         let cyclomatic =
-            MetricsData::project_total(project_data, metrics_thresholds, Complexity::Cyclomatic);
+            MetricsData::project_total(project_data, metrics_config, Complexity::Cyclomatic);
         let cognitive =
-            MetricsData::project_total(project_data, metrics_thresholds, Complexity::Cognitive);
+            MetricsData::project_total(project_data, metrics_config, Complexity::Cognitive);
+        let complexities = complexities(metrics_config, |complexity| {
+            MetricsData::project_total(project_data, metrics_config, complexity)
+        });
 
         Self {
             cyclomatic,
             cognitive,
             coverage,
+            hit_coverage,
+            mutation_score,
+            complexities,
         }
     }
 
-    const fn project_min() -> Self {
+    fn project_min(metrics_config: MetricsConfig) -> Self {
         Self {
-            cyclomatic: MetricsData::project_min(),
-            cognitive: MetricsData::project_min(),
+            cyclomatic: MetricsData::project_min(metrics_config),
+            cognitive: MetricsData::project_min(metrics_config),
             coverage: f64::MAX,
+            hit_coverage: None,
+            mutation_score: None,
+            complexities: complexities(metrics_config, |_| {
+                MetricsData::project_min(metrics_config)
+            }),
         }
     }
 
-    fn update_project_min(mut self, other: Metrics, metrics_thresholds: MetricsThresholds) -> Self {
+    fn update_project_min(mut self, other: &Metrics, metrics_config: MetricsConfig) -> Self {
         self.cyclomatic = self.cyclomatic.update_project_min(
-            other.cyclomatic,
-            metrics_thresholds,
+            &other.cyclomatic,
+            metrics_config,
             Complexity::Cyclomatic,
         );
         self.cognitive = self.cognitive.update_project_min(
-            other.cognitive,
-            metrics_thresholds,
+            &other.cognitive,
+            metrics_config,
             Complexity::Cognitive,
         );
         self.coverage = self.coverage.min(other.coverage);
+        self.hit_coverage = combine(self.hit_coverage, other.hit_coverage, f64::min);
+        self.mutation_score = combine(self.mutation_score, other.mutation_score, f64::min);
+        combine_complexities(
+            &mut self.complexities,
+            &other.complexities,
+            |data, other, complexity| data.update_project_min(other, metrics_config, complexity),
+        );
 
         self
     }
 
-    const fn project_max() -> Self {
+    fn project_max(metrics_config: MetricsConfig) -> Self {
         Self {
-            cyclomatic: MetricsData::project_max(),
-            cognitive: MetricsData::project_max(),
+            cyclomatic: MetricsData::project_max(metrics_config),
+            cognitive: MetricsData::project_max(metrics_config),
             coverage: f64::MIN,
+            hit_coverage: None,
+            mutation_score: None,
+            complexities: complexities(metrics_config, |_| {
+                MetricsData::project_max(metrics_config)
+            }),
         }
     }
 
-    fn update_project_max(mut self, other: Metrics, metrics_thresholds: MetricsThresholds) -> Self {
+    fn update_project_max(mut self, other: &Metrics, metrics_config: MetricsConfig) -> Self {
         self.cyclomatic = self.cyclomatic.update_project_max(
-            other.cyclomatic,
-            metrics_thresholds,
+            &other.cyclomatic,
+            metrics_config,
             Complexity::Cyclomatic,
         );
         self.cognitive = self.cognitive.update_project_max(
-            other.cognitive,
-            metrics_thresholds,
+            &other.cognitive,
+            metrics_config,
             Complexity::Cognitive,
         );
         self.coverage = self.coverage.max(other.coverage);
+        self.hit_coverage = combine(self.hit_coverage, other.hit_coverage, f64::max);
+        self.mutation_score = combine(self.mutation_score, other.mutation_score, f64::max);
+        combine_complexities(
+            &mut self.complexities,
+            &other.complexities,
+            |data, other, complexity| data.update_project_max(other, metrics_config, complexity),
+        );
 
         self
     }
 
+    fn project_sum(metrics_config: MetricsConfig) -> Self {
+        Self {
+            cyclomatic: MetricsData::project_sum(metrics_config),
+            cognitive: MetricsData::project_sum(metrics_config),
+            complexities: complexities(metrics_config, |_| {
+                MetricsData::project_sum(metrics_config)
+            }),
+            ..Self::default()
+        }
+    }
+
     #[inline]
-    fn project_sum(mut self, other: Metrics) -> Self {
-        self.cyclomatic = self.cyclomatic.sum(other.cyclomatic);
-        self.cognitive = self.cognitive.sum(other.cognitive);
+    fn sum(mut self, other: &Metrics) -> Self {
+        self.cyclomatic = self.cyclomatic.sum(&other.cyclomatic);
+        self.cognitive = self.cognitive.sum(&other.cognitive);
         self.coverage += other.coverage;
+        self.hit_coverage = combine(self.hit_coverage, other.hit_coverage, |a, b| a + b);
+        self.mutation_score = combine(self.mutation_score, other.mutation_score, |a, b| a + b);
+        combine_complexities(
+            &mut self.complexities,
+            &other.complexities,
+            |data, other, _| data.sum(other),
+        );
 
         self
     }
 
-    fn project_average(self, num_files: f64, metrics_thresholds: MetricsThresholds) -> Self {
+    fn project_average(self, num_files: f64, metrics_config: MetricsConfig) -> Self {
         let cyclomatic =
             self.cyclomatic
-                .project_average(num_files, metrics_thresholds, Complexity::Cyclomatic);
+                .project_average(num_files, metrics_config, Complexity::Cyclomatic);
         let cognitive =
             self.cognitive
-                .project_average(num_files, metrics_thresholds, Complexity::Cognitive);
+                .project_average(num_files, metrics_config, Complexity::Cognitive);
         let coverage = round_sd(self.coverage / num_files);
+        let hit_coverage = self
+            .hit_coverage
+            .map(|hit_coverage| round_sd(hit_coverage / num_files));
+        // The mutation score is averaged over the mutated files only.
+        let mutation_score = None;
+        let complexities = self
+            .complexities
+            .into_iter()
+            .map(|(complexity, data)| {
+                (
+                    complexity,
+                    data.project_average(num_files, metrics_config, complexity),
+                )
+            })
+            .collect();
 
         Self {
             cyclomatic,
             cognitive,
             coverage,
+            hit_coverage,
+            mutation_score,
+            complexities,
+        }
+    }
+}
+
+// Computes the metrics data of each additional complexity metric.
+#[inline]
+fn complexities(
+    metrics_config: MetricsConfig,
+    metrics_data: impl Fn(Complexity) -> MetricsData,
+) -> BTreeMap<Complexity, MetricsData> {
+    metrics_config
+        .complexities
+        .iter()
+        .map(|&complexity| (complexity, metrics_data(complexity)))
+        .collect()
+}
+
+// Combines the metrics data of the additional complexity metrics.
+#[inline]
+fn combine_complexities(
+    complexities: &mut BTreeMap<Complexity, MetricsData>,
+    other_complexities: &BTreeMap<Complexity, MetricsData>,
+    f: impl Fn(MetricsData, &MetricsData, Complexity) -> MetricsData,
+) {
+    for (complexity, data) in complexities.iter_mut() {
+        if let Some(other) = other_complexities.get(complexity) {
+            *data = f(std::mem::take(data), other, *complexity);
         }
     }
 }
 
 /// Project metrics.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectMetrics {
     /// Total.
     pub total: Metrics,
@@ -447,10 +709,18 @@ pub(crate) struct ProjectData {
     num_spaces: f64,
     ploc: f64,
     covered_lines: f64,
-    wcc_cyclomatic_coverage: f64,
-    wcc_cognitive_coverage: f64,
-    cyclomatic_complexity: f64,
-    cognitive_complexity: f64,
+    hit_covered_lines: f64,
+    hits: f64,
+    wcc_coverage: Complexities,
+    wcc_hit_coverage: Complexities,
+    complexities: Complexities,
+    smells: f64,
+    mutants: f64,
+    killed_mutants: f64,
+    mutation_covered_lines: f64,
+    wcc_mutation_coverage: Complexities,
+    // Lines of the spaces whose complexity does not exceed the Wcc complexity threshold.
+    wcc_ploc: Complexities,
 }
 
 impl ProjectData {
@@ -464,16 +734,25 @@ impl ProjectData {
 
     fn update(&mut self, space_data: &SpaceData) {
         self.covered_lines += space_data.covered_lines;
-        if space_data.cyclomatic_complexity <= WCC_COMPLEXITY_THRESHOLD {
-            self.wcc_cyclomatic_coverage += space_data.covered_lines;
-        }
-        if space_data.cognitive_complexity <= WCC_COMPLEXITY_THRESHOLD {
-            self.wcc_cognitive_coverage += space_data.covered_lines;
+        self.hit_covered_lines += space_data.hit_covered_lines;
+        self.hits += space_data.hits;
+        self.smells += space_data.smells;
+        self.mutants += space_data.mutants;
+        self.killed_mutants += space_data.killed_mutants;
+        let mutation_covered_lines = space_data.mutation_covered_lines();
+        self.mutation_covered_lines += mutation_covered_lines;
+        for complexity in Complexity::VARIANTS {
+            let i = complexity.index();
+            if space_data.complexities[i] <= wcc_complexity_threshold(complexity) {
+                self.wcc_coverage[i] += space_data.covered_lines;
+                self.wcc_hit_coverage[i] += space_data.hit_covered_lines;
+                self.wcc_mutation_coverage[i] += mutation_covered_lines;
+                self.wcc_ploc[i] += space_data.ploc;
+            }
+            self.complexities[i] += space_data.complexities[i];
         }
 
         self.ploc += space_data.ploc;
-        self.cyclomatic_complexity += space_data.cyclomatic_complexity;
-        self.cognitive_complexity += space_data.cognitive_complexity;
     }
 
     #[inline]
@@ -481,15 +760,54 @@ impl ProjectData {
         self.num_spaces += other.num_spaces;
         self.ploc += other.ploc;
         self.covered_lines += other.covered_lines;
-        self.wcc_cyclomatic_coverage += other.wcc_cyclomatic_coverage;
-        self.wcc_cognitive_coverage += other.wcc_cognitive_coverage;
-        self.cyclomatic_complexity += other.cyclomatic_complexity;
-        self.cognitive_complexity += other.cognitive_complexity;
+        self.hit_covered_lines += other.hit_covered_lines;
+        self.hits += other.hits;
+        self.smells += other.smells;
+        self.mutants += other.mutants;
+        self.killed_mutants += other.killed_mutants;
+        self.mutation_covered_lines += other.mutation_covered_lines;
+        for i in 0..Complexity::VARIANTS.len() {
+            self.wcc_coverage[i] += other.wcc_coverage[i];
+            self.wcc_hit_coverage[i] += other.wcc_hit_coverage[i];
+            self.wcc_mutation_coverage[i] += other.wcc_mutation_coverage[i];
+            self.wcc_ploc[i] += other.wcc_ploc[i];
+            self.complexities[i] += other.complexities[i];
+        }
+    }
+
+    // Builds the metrics input, using the average complexity and smells of the spaces.
+    fn input(&self, complexity_type: Complexity) -> MetricInput {
+        let i = complexity_type.index();
+
+        MetricInput {
+            complexity_type,
+            complexity: self.complexities[i] / self.num_spaces,
+            ploc: self.ploc,
+            covered_lines: self.covered_lines,
+            hit_covered_lines: self.hit_covered_lines,
+            hits: self.hits,
+            wcc_covered_lines: self.wcc_coverage[i],
+            wcc_hit_covered_lines: self.wcc_hit_coverage[i],
+            smells: self.smells / self.num_spaces,
+            mutation_covered_lines: self.mutation_covered_lines,
+            wcc_mutation_covered_lines: self.wcc_mutation_coverage[i],
+        }
+    }
+
+    #[inline]
+    fn mutation_score(&self) -> Option<f64> {
+        mutation_score(self.mutants, self.killed_mutants)
     }
 }
 
+// Percentage of killed mutants, if there are any mutants.
+#[inline]
+fn mutation_score(mutants: f64, killed_mutants: f64) -> Option<f64> {
+    (mutants > 0.0).then(|| round_sd((killed_mutants / mutants) * 100.0))
+}
+
 /// Output of the weighted code coverage.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WccOutput {
     /// Files.
     pub files: Vec<FileMetrics>,
@@ -513,24 +831,106 @@ impl WccOutput {
     }
 }
 
+// Code smells of each space, using the space name as the key.
+type SpacesSmells = HashMap<String, Vec<Smell>>;
+
+// Uncovered lines of each space, using the space name as the key.
+type SpacesUncoveredLines = HashMap<String, Vec<LineRange>>;
+
+/// Range of consecutive uncovered lines, which can contain non-instrumented lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LineRange {
+    /// First line of the range.
+    pub start: usize,
+    /// Last line of the range.
+    pub end: usize,
+}
+
 #[derive(Clone, Copy)]
 pub(crate) struct SpaceData {
     ploc: f64,
     covered_lines: f64,
-    cyclomatic_complexity: f64,
-    cognitive_complexity: f64,
+    hit_covered_lines: f64,
+    hits: f64,
+    complexities: Complexities,
+    smells: f64,
+    mutants: f64,
+    killed_mutants: f64,
+    start_line: usize,
+    end_line: usize,
     kind: SpaceKind,
 }
 
+impl SpaceData {
+    // Builds the metrics input of the space.
+    fn input(&self, complexity_type: Complexity) -> MetricInput {
+        let complexity = self.complexities[complexity_type.index()];
+        let mutation_covered_lines = self.mutation_covered_lines();
+        let (wcc_covered_lines, wcc_hit_covered_lines, wcc_mutation_covered_lines) =
+            if complexity > wcc_complexity_threshold(complexity_type) {
+                (0.0, 0.0, 0.0)
+            } else {
+                (
+                    self.covered_lines,
+                    self.hit_covered_lines,
+                    mutation_covered_lines,
+                )
+            };
+
+        MetricInput {
+            complexity_type,
+            complexity,
+            ploc: self.ploc,
+            covered_lines: self.covered_lines,
+            hit_covered_lines: self.hit_covered_lines,
+            hits: self.hits,
+            wcc_covered_lines,
+            wcc_hit_covered_lines,
+            smells: self.smells,
+            mutation_covered_lines,
+            wcc_mutation_covered_lines,
+        }
+    }
+
+    // Covered lines discounted by the fraction of surviving mutants,
+    // since a surviving mutant means that tests do not check those lines.
+    #[inline]
+    fn mutation_covered_lines(&self) -> f64 {
+        if self.mutants > 0.0 {
+            self.covered_lines * (self.killed_mutants / self.mutants)
+        } else {
+            self.covered_lines
+        }
+    }
+}
+
+// Retrieve the name of the innermost space containing the given line
+// among the ones taking part in the metrics computation, so that lines
+// of spaces without any instrumented line, such as closures, are
+// attributed to their enclosing space.
+#[inline]
+fn get_enclosing_space_name(
+    root: &FuncSpace,
+    line: usize,
+    spaces: &HashMap<String, SpaceData>,
+) -> Option<String> {
+    get_enclosing_spaces(root, line)
+        .into_iter()
+        .filter_map(get_space_name)
+        .find(|key| spaces.contains_key(key))
+}
+
 pub(crate) struct Wcc<'a> {
     pub(crate) project_path: &'a Path,
     pub(crate) files: &'a [PathBuf],
     pub(crate) mode: Mode,
-    pub(crate) grcov: Grcov,
-    pub(crate) metrics_thresholds: MetricsThresholds,
+    pub(crate) grcov: &'a Grcov,
+    pub(crate) smells: Smells,
+    pub(crate) mutations: Mutations,
+    pub(crate) metrics_config: MetricsConfig<'a>,
     pub(crate) files_metrics: Mutex<Vec<FileMetrics>>,
     pub(crate) ignored_files: Mutex<Vec<String>>,
-    pub(crate) sort_by: Sort,
+    pub(crate) sort_by: &'a Sort,
 }
 
 impl<'a> Wcc<'a> {
@@ -544,17 +944,16 @@ impl<'a> Wcc<'a> {
     }
 
     fn sort_output(&self) -> Result<()> {
-        let sort = |a: Metrics, b: Metrics| match self.sort_by {
-            Sort::Wcc => b.cyclomatic.wcc.total_cmp(&a.cyclomatic.wcc),
-            Sort::Crap => b.cyclomatic.crap.total_cmp(&a.cyclomatic.crap),
-            Sort::Skunk => b.cyclomatic.skunk.total_cmp(&a.cyclomatic.skunk),
+        let sort = |a: &Metrics, b: &Metrics| {
+            b.sort_score(self.sort_by)
+                .total_cmp(&a.sort_score(self.sort_by))
         };
 
         let mut files_metrics = self.files_metrics.lock()?;
-        files_metrics.sort_by(|a, b| sort(a.metrics, b.metrics));
+        files_metrics.sort_by(|a, b| sort(&a.metrics, &b.metrics));
         files_metrics.iter_mut().for_each(|fm| {
             if let Some(ref mut functions) = fm.functions {
-                functions.sort_by(|a, b| sort(a.metrics, b.metrics));
+                functions.sort_by(|a, b| sort(&a.metrics, &b.metrics));
             }
         });
 
@@ -568,8 +967,10 @@ impl<'a> Wcc<'a> {
         &self,
         space: &FuncSpace,
         spaces: &mut HashMap<String, SpaceData>,
-        line_is_covered: bool,
+        line_hits: i32,
     ) {
+        let line_is_covered = line_hits != 0;
+        let line_weight = hit_weight(line_hits);
         if let Some(key) = get_space_name(space) {
             spaces
                 .entry(key.to_owned())
@@ -578,12 +979,20 @@ impl<'a> Wcc<'a> {
                     if line_is_covered {
                         space_data.covered_lines += 1.0;
                     }
+                    space_data.hit_covered_lines += line_weight;
+                    space_data.hits += line_hits as f64;
                 })
                 .or_insert(SpaceData {
                     ploc: 1.0,
                     covered_lines: if line_is_covered { 1.0 } else { 0.0 },
-                    cyclomatic_complexity: space.metrics.cyclomatic.cyclomatic_sum(),
-                    cognitive_complexity: space.metrics.cognitive.cognitive_sum(),
+                    hit_covered_lines: line_weight,
+                    hits: line_hits as f64,
+                    complexities: get_complexities(space),
+                    smells: 0.0,
+                    mutants: 0.0,
+                    killed_mutants: 0.0,
+                    start_line: space.start_line,
+                    end_line: space.end_line,
                     kind: space.kind,
                 });
         }
@@ -592,6 +1001,8 @@ impl<'a> Wcc<'a> {
     fn get_functions_metrics(
         &self,
         spaces: HashMap<String, SpaceData>,
+        mut smells: SpacesSmells,
+        mut uncovered_lines: SpacesUncoveredLines,
     ) -> Option<Vec<FunctionMetrics>> {
         if let Mode::Files = self.mode {
             return None;
@@ -601,7 +1012,15 @@ impl<'a> Wcc<'a> {
             .into_iter()
             .filter(|(_, data)| data.kind == SpaceKind::Function)
             .map(|(name, space_data)| {
-                FunctionMetrics::new(name, space_data, self.metrics_thresholds)
+                let function_smells = smells.remove(&name).unwrap_or_default();
+                let function_uncovered_lines = uncovered_lines.remove(&name).unwrap_or_default();
+                FunctionMetrics::new(
+                    name,
+                    space_data,
+                    function_smells,
+                    function_uncovered_lines,
+                    self.metrics_config,
+                )
             })
             .collect();
 
@@ -612,6 +1031,8 @@ impl<'a> Wcc<'a> {
         &self,
         file: &Path,
         spaces: HashMap<String, SpaceData>,
+        smells: SpacesSmells,
+        uncovered_lines: SpacesUncoveredLines,
     ) -> Result<ProjectData> {
         let mut project_data = ProjectData::new(spaces.len() as f64);
         spaces
@@ -623,32 +1044,95 @@ impl<'a> Wcc<'a> {
             files_metrics.push(FileMetrics::new(
                 name.to_owned(),
                 project_data,
-                self.metrics_thresholds,
-                self.get_functions_metrics(spaces),
+                self.metrics_config,
+                self.get_functions_metrics(spaces, smells, uncovered_lines),
             ));
         }
 
         Ok(project_data)
     }
 
+    // Attributes the code smells of a file to the spaces containing them.
+    fn update_smells(
+        &self,
+        root: &FuncSpace,
+        file: &Path,
+        spaces: &mut HashMap<String, SpaceData>,
+    ) -> SpacesSmells {
+        let mut spaces_smells = SpacesSmells::new();
+        for smell in self.smells.get(file) {
+            if let Some(key) = get_enclosing_space_name(root, smell.line, spaces) {
+                if let Some(space_data) = spaces.get_mut(&key) {
+                    space_data.smells += 1.0;
+                }
+                spaces_smells.entry(key).or_default().push(smell.clone());
+            }
+        }
+
+        spaces_smells
+    }
+
+    // Attributes the mutants of a file to the spaces containing them.
+    fn update_mutants(
+        &self,
+        root: &FuncSpace,
+        file: &Path,
+        spaces: &mut HashMap<String, SpaceData>,
+    ) {
+        for mutant in self.mutations.get(file) {
+            if let Some(space_data) = get_enclosing_space_name(root, mutant.line, spaces)
+                .and_then(|key| spaces.get_mut(&key))
+            {
+                space_data.mutants += 1.0;
+                if mutant.killed {
+                    space_data.killed_mutants += 1.0;
+                }
+            }
+        }
+    }
+
     fn get_spaces(
         &self,
         file: &Path,
         lines_coverage: &[Option<i32>],
-    ) -> Result<HashMap<String, SpaceData>> {
+    ) -> Result<(
+        HashMap<String, SpaceData>,
+        SpacesSmells,
+        SpacesUncoveredLines,
+    )> {
         let mut spaces: HashMap<String, SpaceData> = HashMap::new();
+        let mut uncovered_lines = SpacesUncoveredLines::new();
         let root = get_root(file)?;
 
+        // Space of the previous instrumented line, if it is uncovered.
+        let mut uncovered_space = None;
         for (line, coverage) in lines_coverage
             .iter()
             .enumerate()
             .filter_map(|(line, coverage)| coverage.map(|cov| (line, cov)))
         {
             let space = get_line_space(&root, line);
-            self.update_spaces(space, &mut spaces, coverage != 0);
+            self.update_spaces(space, &mut spaces, coverage);
+
+            let key = get_space_name(space).filter(|_| coverage == 0);
+            if let Some(key) = &key {
+                // Lines are the 0-based indexes of the coverage data.
+                let number = line + 1;
+                let ranges = uncovered_lines.entry(key.clone()).or_default();
+                match ranges.last_mut() {
+                    Some(range) if uncovered_space.as_ref() == Some(key) => range.end = number,
+                    _ => ranges.push(LineRange {
+                        start: number,
+                        end: number,
+                    }),
+                }
+            }
+            uncovered_space = key;
         }
+        let smells = self.update_smells(&root, file, &mut spaces);
+        self.update_mutants(&root, file, &mut spaces);
 
-        Ok(spaces)
+        Ok((spaces, smells, uncovered_lines))
     }
 
     fn compute_metrics(&self, file: &Path) -> Option<ProjectData> {
@@ -658,16 +1142,17 @@ impl<'a> Wcc<'a> {
             self.update_ignored_files(file).ok()?;
             return None;
         };
-        let spaces = self.get_spaces(file, lines_coverage).ok()?;
+        let (spaces, smells, uncovered_lines) = self.get_spaces(file, lines_coverage).ok()?;
 
-        self.compute_file_metrics(file, spaces).ok()
+        self.compute_file_metrics(file, spaces, smells, uncovered_lines)
+            .ok()
     }
 
     fn get_project_min(&self) -> Result<Metrics> {
         let metrics = self.files_metrics.lock()?.iter().fold(
-            Metrics::project_min(),
+            Metrics::project_min(self.metrics_config),
             |min_metrics, file_metrics| {
-                min_metrics.update_project_min(file_metrics.metrics, self.metrics_thresholds)
+                min_metrics.update_project_min(&file_metrics.metrics, self.metrics_config)
             },
         );
 
@@ -676,9 +1161,9 @@ impl<'a> Wcc<'a> {
 
     fn get_project_max(&self) -> Result<Metrics> {
         let metrics = self.files_metrics.lock()?.iter().fold(
-            Metrics::project_max(),
+            Metrics::project_max(self.metrics_config),
             |max_metrics, file_metrics| {
-                max_metrics.update_project_max(file_metrics.metrics, self.metrics_thresholds)
+                max_metrics.update_project_max(&file_metrics.metrics, self.metrics_config)
             },
         );
 
@@ -687,17 +1172,28 @@ impl<'a> Wcc<'a> {
 
     fn get_project_average(&self) -> Result<Metrics> {
         let files_metrics = self.files_metrics.lock()?;
-        let sum_metrics = files_metrics
+        let sum_metrics = files_metrics.iter().fold(
+            Metrics::project_sum(self.metrics_config),
+            |sum_metrics, file_metrics| sum_metrics.sum(&file_metrics.metrics),
+        );
+
+        let mutated_files = files_metrics
             .iter()
-            .fold(Metrics::default(), |sum_metrics, file_metrics| {
-                sum_metrics.project_sum(file_metrics.metrics)
-            });
+            .filter(|file_metrics| file_metrics.metrics.mutation_score.is_some())
+            .count();
+        let mutation_score = sum_metrics
+            .mutation_score
+            .map(|mutation_score| round_sd(mutation_score / mutated_files as f64));
+
+        let mut average_metrics =
+            sum_metrics.project_average(files_metrics.len() as f64, self.metrics_config);
+        average_metrics.mutation_score = mutation_score;
 
-        Ok(sum_metrics.project_average(files_metrics.len() as f64, self.metrics_thresholds))
+        Ok(average_metrics)
     }
 
     fn get_project_metrics(&self, project_data: ProjectData) -> Result<ProjectMetrics> {
-        let total = Metrics::project_total(project_data, self.metrics_thresholds);
+        let total = Metrics::project_total(project_data, self.metrics_config);
         let min = self.get_project_min()?;
         let max = self.get_project_max()?;
         let average = self.get_project_average()?;
@@ -0,0 +1,146 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    metrics::{
+        crap::crap,
+        first_passing, invert,
+        skunk::skunk,
+        wcc::{wcc, wcc_complexity_threshold},
+        MetricInput, MetricsThresholds,
+    },
+    Complexity,
+};
+
+use super::{ProjectData, SpaceData};
+
+/// Coverage needed to pass the thresholds of the metrics.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetsData {
+    /// Minimum coverage at which CRAP does not exceed its threshold,
+    /// missing when the complexity alone exceeds it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crap_coverage: Option<f64>,
+    /// Minimum coverage at which Skunk does not exceed its threshold,
+    /// missing when complexity and code smells alone exceed it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skunk_coverage: Option<f64>,
+    /// Uncovered lines to cover so that Wcc reaches its threshold, missing when the
+    /// lines of the spaces not exceeding the Wcc complexity threshold are not enough.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wcc_lines: Option<usize>,
+}
+
+impl TargetsData {
+    // Inverts the CRAP, Skunk and Wcc formulas, where `wcc_ploc` are the lines
+    // of the spaces not exceeding the Wcc complexity threshold.
+    fn new(input: MetricInput, wcc_ploc: f64, thresholds: MetricsThresholds) -> Self {
+        let complexity_type = input.complexity_type;
+        // Lines are whole numbers, even if they are stored as floats.
+        let wcc_covered_lines = input.wcc_covered_lines as usize;
+
+        Self {
+            crap_coverage: invert(0.0, 100.0, |coverage| {
+                crap(coverage / 100.0, input.complexity) <= thresholds.crap(complexity_type)
+            }),
+            skunk_coverage: invert(0.0, 100.0, |coverage| {
+                skunk(coverage / 100.0, input.complexity, input.smells)
+                    <= thresholds.skunk(complexity_type)
+            }),
+            wcc_lines: first_passing(wcc_covered_lines, wcc_ploc as usize, |lines| {
+                wcc(lines as f64, input.ploc) >= thresholds.wcc()
+            })
+            .map(|lines| lines - wcc_covered_lines),
+        }
+    }
+}
+
+/// Coverage targets, computed with cyclomatic and cognitive complexity.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Targets {
+    /// Cyclomatic.
+    pub cyclomatic: TargetsData,
+    /// Cognitive.
+    pub cognitive: TargetsData,
+}
+
+impl Targets {
+    pub(crate) fn file(project_data: ProjectData, thresholds: MetricsThresholds) -> Self {
+        let data = |complexity_type: Complexity| {
+            TargetsData::new(
+                project_data.input(complexity_type),
+                project_data.wcc_ploc[complexity_type.index()],
+                thresholds,
+            )
+        };
+
+        Self {
+            cyclomatic: data(Complexity::Cyclomatic),
+            cognitive: data(Complexity::Cognitive),
+        }
+    }
+
+    pub(crate) fn function(space_data: SpaceData, thresholds: MetricsThresholds) -> Self {
+        let data = |complexity_type: Complexity| {
+            let input = space_data.input(complexity_type);
+            let wcc_ploc = if input.complexity > wcc_complexity_threshold(complexity_type) {
+                0.0
+            } else {
+                input.ploc
+            };
+            TargetsData::new(input, wcc_ploc, thresholds)
+        };
+
+        Self {
+            cyclomatic: data(Complexity::Cyclomatic),
+            cognitive: data(Complexity::Cognitive),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(complexity: f64, ploc: f64, covered_lines: f64) -> MetricInput {
+        MetricInput {
+            complexity_type: Complexity::Cyclomatic,
+            complexity,
+            ploc,
+            covered_lines,
+            hit_covered_lines: covered_lines,
+            hits: covered_lines,
+            wcc_covered_lines: covered_lines,
+            wcc_hit_covered_lines: covered_lines,
+            smells: 0.0,
+            mutation_covered_lines: covered_lines,
+            wcc_mutation_covered_lines: covered_lines,
+        }
+    }
+
+    #[test]
+    fn inverts_the_metrics() {
+        let thresholds = MetricsThresholds::default();
+        let targets = TargetsData::new(input(12.0, 20.0, 5.0), 20.0, thresholds);
+
+        // CRAP 16.4 and Skunk 16.66 are the scores of complexity 10 at 60% coverage.
+        assert_eq!(targets.crap_coverage, Some(68.7));
+        assert_eq!(targets.skunk_coverage, Some(76.8));
+        assert!(crap(0.687, 12.0) <= thresholds.crap(Complexity::Cyclomatic));
+        assert!(crap(0.686, 12.0) > thresholds.crap(Complexity::Cyclomatic));
+        // Wcc reaches 60% with 12 of the 20 lines covered.
+        assert_eq!(targets.wcc_lines, Some(7));
+    }
+
+    #[test]
+    fn unreachable_targets() {
+        let thresholds = MetricsThresholds::default();
+        let targets = TargetsData::new(input(20.0, 20.0, 5.0), 0.0, thresholds);
+
+        assert_eq!(targets.crap_coverage, None);
+        assert_eq!(targets.skunk_coverage, None);
+        assert_eq!(targets.wcc_lines, None);
+    }
+}
@@ -6,28 +6,52 @@ use thiserror::Error;
 /// Customized error messages using thiserror library.
 #[derive(Error, Debug)]
 pub enum Error {
+    /// Error while reading or writing a file.
     #[error("Error while reading Files from project folder")]
     WrongFile(#[from] std::io::Error),
+    /// Error while stripping the project path prefix.
     #[error("Error while stripping the prefix")]
     StripPrefix(#[from] StripPrefixError),
+    /// Error while parsing or writing a json file.
     #[error("Error while reading json")]
     WrongJSONFile(#[from] serde_json::Error),
+    /// Error while converting a json value.
     #[error("Error while converting JSON value to a type")]
     Conversion,
+    /// Error while computing the metrics.
     #[error("Error while computing Metrics")]
     Metrics,
+    /// Error while guessing the language of a file.
     #[error("Error while guessing language")]
     Language,
+    /// Error during concurrent processing.
     #[error("Error during concurrency")]
     Concurrent,
+    /// Invalid output path.
     #[error("{0}")]
     OutputPath(&'static str),
+    /// Invalid combination of options.
+    #[error("{0}")]
+    Options(String),
+    /// Error while locking a mutex.
     #[error("Error while locking mutex")]
     Mutex,
+    /// Error while sending a job to a thread.
     #[error("Error while sending job via sender")]
     Sender,
+    /// Error while rendering the html output.
     #[error("Error while creating HTML file")]
     Html(#[from] minijinja::Error),
+    /// Json output with an incompatible schema version.
+    #[error("Incompatible json output schema version: {0:?}")]
+    SchemaVersion(String),
+    /// Error while writing a CSV or TSV file.
+    #[error("Error while writing CSV file")]
+    Csv(#[from] csv::Error),
+    /// Run missing from the history, lacking the requested metrics,
+    /// or stored with an incompatible schema version.
+    #[error("{0}")]
+    History(String),
 }
 
 pub(crate) type Result<T> = ::std::result::Result<T, Error>;
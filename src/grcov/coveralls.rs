@@ -1,37 +1,139 @@
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use std::{
-    collections::HashMap,
-    fs,
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::File,
+    io::BufReader,
     path::{Path, PathBuf},
 };
 
 use crate::error::Result;
 
+// Fields not listed here, such as `source` or `source_digest`,
+// are skipped by the deserializer without being allocated.
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct CoverallsSourceFile {
     pub(crate) name: PathBuf,
     pub(crate) coverage: Vec<Option<i32>>,
 }
 
-#[derive(Debug, Deserialize)]
-struct CoverallsJson {
-    source_files: Vec<CoverallsSourceFile>,
-}
-
 #[derive(Debug, Serialize)]
 pub(crate) struct Coveralls(pub(crate) HashMap<PathBuf, CoverallsSourceFile>);
 
 impl Coveralls {
-    pub(crate) fn new(json_path: &Path, project_path: &Path) -> Result<Coveralls> {
-        let json = fs::read_to_string(json_path)?;
-        let coveralls_json: CoverallsJson = serde_json::from_str(&json)?;
-        let mut coveralls = Coveralls(HashMap::new());
+    // Streams the coveralls json file, keeping only the coverage
+    // of the `files` discovered in the project.
+    pub(crate) fn new(
+        json_path: &Path,
+        project_path: &Path,
+        files: &[PathBuf],
+    ) -> Result<Coveralls> {
+        let reader = BufReader::new(File::open(json_path)?);
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        let files = files.iter().map(PathBuf::as_path).collect::<HashSet<_>>();
 
-        coveralls_json.source_files.into_iter().for_each(|file| {
-            coveralls.0.insert(project_path.join(&file.name), file);
-        });
+        let source_files = CoverallsSeed {
+            project_path,
+            files: &files,
+        }
+        .deserialize(&mut deserializer)?;
+        deserializer.end()?;
 
-        Ok(coveralls)
+        Ok(Coveralls(source_files))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum CoverallsField {
+    SourceFiles,
+    #[serde(other)]
+    Other,
+}
+
+// Visits the top-level coveralls object, deserializing only `source_files`.
+#[derive(Clone, Copy)]
+struct CoverallsSeed<'a> {
+    project_path: &'a Path,
+    files: &'a HashSet<&'a Path>,
+}
+
+impl<'de> DeserializeSeed<'de> for CoverallsSeed<'_> {
+    type Value = HashMap<PathBuf, CoverallsSourceFile>;
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for CoverallsSeed<'_> {
+    type Value = HashMap<PathBuf, CoverallsSourceFile>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a coveralls json object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut source_files = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                CoverallsField::SourceFiles => {
+                    source_files = Some(map.next_value_seed(SourceFilesSeed(self))?);
+                }
+                CoverallsField::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        source_files.ok_or_else(|| de::Error::missing_field("source_files"))
+    }
+}
+
+// Visits the `source_files` array one file at a time,
+// dropping the files which are not part of the project.
+struct SourceFilesSeed<'a>(CoverallsSeed<'a>);
+
+impl<'de> DeserializeSeed<'de> for SourceFilesSeed<'_> {
+    type Value = HashMap<PathBuf, CoverallsSourceFile>;
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SourceFilesSeed<'_> {
+    type Value = HashMap<PathBuf, CoverallsSourceFile>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of coveralls source files")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut source_files = HashMap::new();
+        while let Some(file) = seq.next_element::<CoverallsSourceFile>()? {
+            let file_path = self.0.project_path.join(&file.name);
+            if self.0.files.contains(file_path.as_path()) {
+                source_files.insert(file_path, file);
+            }
+        }
+
+        Ok(source_files)
     }
 }
 
@@ -39,23 +141,26 @@ impl Coveralls {
 mod tests {
 
     use super::Coveralls;
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
 
     const COVERALLS_PATH: &str = "./tests/grcov_files/grcov_coveralls.json";
 
     #[test]
     fn test_coveralls() {
-        let coveralls =
-            Coveralls::new(Path::new(COVERALLS_PATH), Path::new("project/test/path/")).unwrap();
+        let files = [
+            PathBuf::from("project/test/path/src/app.rs"),
+            PathBuf::from("project/test/path/src/error.rs"),
+        ];
+        let coveralls = Coveralls::new(
+            Path::new(COVERALLS_PATH),
+            Path::new("project/test/path/"),
+            &files,
+        )
+        .unwrap();
 
         insta::with_settings!({sort_maps => true}, {
             insta::assert_yaml_snapshot!(coveralls, @r###"
             ---
-            project/test/path/examples/single_app.rs:
-              name: examples/single_app.rs
-              coverage:
-                - ~
-                - 0
             project/test/path/src/app.rs:
               name: src/app.rs
               coverage:
@@ -0,0 +1,364 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    concurrent::{Metrics, MetricsData, ProjectMetrics, WccOutput},
+    error::{Error, Result},
+    output::{major_version, SCHEMA_VERSION},
+    Complexity, Mode,
+};
+
+// Number of characters of the commit hashes shown in the runs list.
+const SHORT_COMMIT: usize = 7;
+// Number of changed files listed when comparing two runs.
+const COMPARED_FILES: usize = 20;
+
+/// Summary of a file stored in the history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSummary {
+    /// File name.
+    pub name: String,
+    /// File metrics.
+    pub metrics: Metrics,
+}
+
+/// Run of the tool stored in the history.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    /// Version of the json output schema of the metrics.
+    pub schema_version: String,
+    /// Seconds since the Unix epoch at which the run has been stored.
+    pub timestamp: u64,
+    /// Commit hash of the analyzed code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Label of the run, e.g. the sprint name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Analysis mode.
+    pub mode: Mode,
+    /// Project metrics.
+    pub project: ProjectMetrics,
+    /// Metrics of the analyzed files.
+    pub files: Vec<FileSummary>,
+}
+
+impl HistoryEntry {
+    pub(crate) fn new(
+        wcc_output: &WccOutput,
+        mode: Mode,
+        commit: Option<&str>,
+        label: Option<&str>,
+    ) -> Self {
+        let mut files: Vec<_> = wcc_output
+            .files
+            .iter()
+            .map(|file| FileSummary {
+                name: file.name.clone(),
+                metrics: file.metrics.clone(),
+            })
+            .collect();
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            schema_version: SCHEMA_VERSION.to_owned(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+            commit: commit.map(str::to_owned),
+            label: label.map(str::to_owned),
+            mode,
+            project: ProjectMetrics {
+                total: wcc_output.project.total.clone(),
+                min: wcc_output.project.min.clone(),
+                max: wcc_output.project.max.clone(),
+                average: wcc_output.project.average.clone(),
+            },
+            files,
+        }
+    }
+
+    /// Name of the run: its label, its short commit hash, or its date.
+    pub fn name(&self) -> String {
+        self.label
+            .clone()
+            .or_else(|| self.commit.as_deref().map(short_commit))
+            .unwrap_or_else(|| format_timestamp(self.timestamp))
+    }
+}
+
+/// Runs of the tool, stored in an append-only JSON Lines file
+/// from the oldest to the most recent one.
+#[derive(Debug, Default)]
+pub struct History {
+    /// Stored runs.
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Loads the history file, which is empty when it does not exist, checking
+    /// that each run has the same major version of [`SCHEMA_VERSION`].
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(error.into()),
+        };
+        let mut entries = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let json: serde_json::Value = serde_json::from_str(line)?;
+            let schema_version = json
+                .get("schemaVersion")
+                .and_then(|version| version.as_str())
+                .unwrap_or_default();
+            if major_version(schema_version) != major_version(SCHEMA_VERSION) {
+                return Err(Error::History(format!(
+                    "History run at line {} has the incompatible schema version {schema_version:?}",
+                    index + 1
+                )));
+            }
+            entries.push(serde_json::from_value(json)?);
+        }
+
+        Ok(Self { entries })
+    }
+
+    // Appends a run to the history file, creating it if needed.
+    pub(crate) fn append(path: &Path, entry: &HistoryEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(line.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Finds a run by its index in the list, starting from 1, by a prefix
+    /// of its commit hash, or by its label. The most recent run wins.
+    pub fn find(&self, key: &str) -> Option<&HistoryEntry> {
+        if let Ok(index) = key.parse::<usize>() {
+            if let Some(entry) = index.checked_sub(1).and_then(|i| self.entries.get(i)) {
+                return Some(entry);
+            }
+        }
+
+        self.entries.iter().rev().find(|entry| {
+            entry.label.as_deref() == Some(key)
+                || entry
+                    .commit
+                    .as_deref()
+                    .is_some_and(|commit| !key.is_empty() && commit.starts_with(key))
+        })
+    }
+
+    /// Lists the runs with their project metrics computed with the given complexity metric.
+    pub fn format_runs(&self, complexity: Complexity) -> String {
+        let mut output = format!(
+            "{:>4}  {:<16}  {:<8}  {:<16}  {:>9}  {:>8}  {:>8}  {:>8}  {:>6}\n",
+            "#", "Date", "Commit", "Label", "Coverage", "Wcc", "CRAP", "Skunk", "Files"
+        );
+        for (index, entry) in self.entries.iter().enumerate() {
+            // Runs lacking the complexity metric are listed without its values.
+            let (wcc, crap, skunk) = get_project_data(&entry.project, complexity).map_or_else(
+                || ("-".to_owned(), "-".to_owned(), "-".to_owned()),
+                |(total, average)| {
+                    (
+                        format!("{}%", total.wcc),
+                        average.crap.to_string(),
+                        average.skunk.to_string(),
+                    )
+                },
+            );
+            let _ = writeln!(
+                output,
+                "{:>4}  {:<16}  {:<8}  {:<16}  {:>8}%  {:>8}  {:>8}  {:>8}  {:>6}",
+                index + 1,
+                format_timestamp(entry.timestamp),
+                entry.commit.as_deref().map_or("-".to_owned(), short_commit),
+                entry.label.as_deref().unwrap_or("-"),
+                entry.project.total.coverage,
+                wcc,
+                crap,
+                skunk,
+                entry.files.len(),
+            );
+        }
+
+        output
+    }
+
+    /// Compares two runs: the project metrics, computed with the given complexity metric,
+    /// followed by the files whose Wcc changed the most, and the added and removed files.
+    pub fn format_comparison(
+        base: &HistoryEntry,
+        head: &HistoryEntry,
+        complexity: Complexity,
+    ) -> Result<String> {
+        let missing = || Error::History(format!("History runs lack the {complexity} metric"));
+        let (base_total, base_average) =
+            get_project_data(&base.project, complexity).ok_or_else(missing)?;
+        let (head_total, head_average) =
+            get_project_data(&head.project, complexity).ok_or_else(missing)?;
+
+        let mut output = format!(
+            "Comparing {} with {} using {complexity} complexity\n\n{:<10}  {:>9}  {:>9}  {:>9}\n",
+            base.name(),
+            head.name(),
+            "Metric",
+            "Base",
+            "Head",
+            "Delta"
+        );
+        for (metric, base_value, head_value) in [
+            (
+                "Coverage",
+                base.project.total.coverage,
+                head.project.total.coverage,
+            ),
+            ("Wcc", base_total.wcc, head_total.wcc),
+            ("CRAP", base_average.crap, head_average.crap),
+            ("Skunk", base_average.skunk, head_average.skunk),
+        ] {
+            let _ = writeln!(
+                output,
+                "{metric:<10}  {base_value:>9}  {head_value:>9}  {:>+9.1}",
+                head_value - base_value
+            );
+        }
+
+        let base_files: BTreeMap<_, _> = base
+            .files
+            .iter()
+            .map(|file| (file.name.as_str(), &file.metrics))
+            .collect();
+        let head_files: BTreeMap<_, _> = head
+            .files
+            .iter()
+            .map(|file| (file.name.as_str(), &file.metrics))
+            .collect();
+
+        let mut changed: Vec<_> = head_files
+            .iter()
+            .filter_map(|(name, head_metrics)| {
+                let base_wcc = metrics_data(base_files.get(name)?, complexity)?.wcc;
+                let head_wcc = metrics_data(head_metrics, complexity)?.wcc;
+                (base_wcc != head_wcc).then_some((*name, base_wcc, head_wcc))
+            })
+            .collect();
+        changed.sort_by(|a, b| {
+            (b.2 - b.1)
+                .abs()
+                .total_cmp(&(a.2 - a.1).abs())
+                .then_with(|| a.0.cmp(b.0))
+        });
+        if !changed.is_empty() {
+            let _ = writeln!(output, "\nWcc changes ({} files)", changed.len());
+            for (name, base_wcc, head_wcc) in changed.iter().take(COMPARED_FILES) {
+                let _ = writeln!(
+                    output,
+                    "  {name}: {base_wcc}% -> {head_wcc}% ({:+.1})",
+                    head_wcc - base_wcc
+                );
+            }
+        }
+
+        for (title, files, other) in [
+            ("Added files", &head_files, &base_files),
+            ("Removed files", &base_files, &head_files),
+        ] {
+            let names: Vec<_> = files
+                .keys()
+                .filter(|name| !other.contains_key(*name))
+                .collect();
+            if !names.is_empty() {
+                let _ = writeln!(output, "\n{title} ({})", names.len());
+                for name in names {
+                    let _ = writeln!(output, "  {name}");
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+// Metrics data of the given complexity metric.
+#[inline]
+pub(crate) fn metrics_data(metrics: &Metrics, complexity: Complexity) -> Option<&MetricsData> {
+    match complexity {
+        Complexity::Cyclomatic => Some(&metrics.cyclomatic),
+        Complexity::Cognitive => Some(&metrics.cognitive),
+        _ => metrics.complexities.get(&complexity),
+    }
+}
+
+// Total and average project metrics data of the given complexity metric.
+#[inline]
+fn get_project_data(
+    project: &ProjectMetrics,
+    complexity: Complexity,
+) -> Option<(&MetricsData, &MetricsData)> {
+    Some((
+        metrics_data(&project.total, complexity)?,
+        metrics_data(&project.average, complexity)?,
+    ))
+}
+
+#[inline]
+fn short_commit(commit: &str) -> String {
+    commit.chars().take(SHORT_COMMIT).collect()
+}
+
+// Formats seconds since the Unix epoch as an UTC date and time,
+// converting days to the proleptic Gregorian calendar.
+pub(crate) fn format_timestamp(timestamp: u64) -> String {
+    let (days, seconds) = (timestamp / 86_400, timestamp % 86_400);
+    // Days since 0000-03-01, so that leap days are at the end of the years.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3_600,
+        seconds % 3_600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_timestamp;
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_timestamp(1_792_327_380), "2026-10-18 12:43");
+    }
+}
@@ -7,6 +7,9 @@
 //!
 //! - Cyclomatic
 //! - Cognitive
+//! - Halstead effort and difficulty
+//! - Maintainability Index
+//! - Number of exits and arguments
 //!
 //! The tool implements the following algorithms:
 //!
@@ -17,8 +20,11 @@
 mod concurrent;
 mod error;
 mod grcov;
+mod history;
 mod metrics;
+mod mutations;
 mod output;
+mod smells;
 
 use std::{
     ffi::OsStr,
@@ -29,12 +35,32 @@ use std::{
     sync::Mutex,
 };
 
-use concurrent::{Grcov, Wcc, WccConcurrent, WccOutput};
-use error::{Error, Result};
+use concurrent::{Grcov, Wcc, WccConcurrent};
+use error::Result;
 use grcov::{covdir::Covdir, coveralls::Coveralls};
-use metrics::MetricsThresholds;
-use output::{HtmlPrinter, JsonPrinter, WccPrinter};
-use serde::Serialize;
+use metrics::{complexity_threshold, Complexities, MetricsConfig, MetricsThresholds};
+use mutations::Mutations;
+use output::{
+    BadgePrinter, CodeClimatePrinter, CsvPrinter, HtmlPrinter, JsonPrinter, JunitPrinter,
+    MarkdownPrinter, PrioritiesPrinter, SarifPrinter, SonarCoveragePrinter, SonarIssuesPrinter,
+    TablePrinter, WccPrinter, CSV_DELIMITER, DEFAULT_MARKDOWN_TOP, DEFAULT_PRIORITIES_TOP,
+    TSV_DELIMITER,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smells::Smells;
+
+pub use concurrent::{
+    files::FileMetrics,
+    functions::FunctionMetrics,
+    targets::{Targets, TargetsData},
+    LineRange, MetricValue, Metrics, MetricsData, ProjectMetrics, WccOutput,
+};
+pub use error::Error;
+pub use history::{FileSummary, History, HistoryEntry};
+pub use metrics::{Metric, MetricInput};
+pub use output::{ReportThresholds, WccReport, SCHEMA_VERSION};
+pub use smells::Smell;
 
 #[derive(Debug)]
 struct Parameters<'a> {
@@ -42,8 +68,33 @@ struct Parameters<'a> {
     mode: Mode,
     thresholds: MetricsThresholds,
     sort_by: Sort,
+    output: Output,
+    hit_weighted: bool,
+    custom_metrics: Vec<Box<dyn Metric>>,
+    complexities: Vec<Complexity>,
+    smells_file: Option<SmellsFile<&'a Path>>,
+    mutations_file: Option<MutationsFile<&'a Path>>,
     json_path: Option<&'a Path>,
     html_path: Option<&'a Path>,
+    html_templates: Option<&'a Path>,
+    html_single: bool,
+    #[cfg(feature = "offline-html")]
+    html_offline: bool,
+    sarif_path: Option<&'a Path>,
+    csv_path: Option<&'a Path>,
+    tsv_path: Option<&'a Path>,
+    junit_path: Option<&'a Path>,
+    markdown_path: Option<&'a Path>,
+    code_climate_path: Option<&'a Path>,
+    badges: Option<Complexity>,
+    sonar_issues_path: Option<&'a Path>,
+    sonar_coverage_path: Option<&'a Path>,
+    markdown_top: usize,
+    markdown_max_size: Option<usize>,
+    priorities_top: usize,
+    history_path: Option<&'a Path>,
+    history_commit: Option<&'a str>,
+    history_label: Option<&'a str>,
 }
 
 impl Default for Parameters<'_> {
@@ -53,8 +104,33 @@ impl Default for Parameters<'_> {
             n_threads: (rayon::current_num_threads() - 1).max(1),
             mode: Mode::default(),
             sort_by: Sort::default(),
+            output: Output::default(),
+            hit_weighted: false,
+            custom_metrics: Vec::new(),
+            complexities: Vec::new(),
+            smells_file: None,
+            mutations_file: None,
             json_path: Option::default(),
             html_path: Option::default(),
+            html_templates: Option::default(),
+            html_single: false,
+            #[cfg(feature = "offline-html")]
+            html_offline: false,
+            sarif_path: Option::default(),
+            csv_path: Option::default(),
+            tsv_path: Option::default(),
+            junit_path: Option::default(),
+            markdown_path: Option::default(),
+            code_climate_path: Option::default(),
+            badges: Option::default(),
+            sonar_issues_path: Option::default(),
+            sonar_coverage_path: Option::default(),
+            markdown_top: DEFAULT_MARKDOWN_TOP,
+            markdown_max_size: Option::default(),
+            priorities_top: DEFAULT_PRIORITIES_TOP,
+            history_path: Option::default(),
+            history_commit: Option::default(),
+            history_label: Option::default(),
         }
     }
 }
@@ -67,6 +143,13 @@ impl Default for Parameters<'_> {
 /// * *coveralls* as default format for the input grcov json file.
 /// * *files* as default analysis mode.
 /// * *wcc plain* as default metric that will be used to sort the output.
+/// * *no report* on the standard output.
+/// * *no hit-weighted metrics*, unless a hit-weighted sort metric is chosen.
+/// * *no code smells* when computing the Skunk metric.
+/// * *no mutation-adjusted metrics*.
+/// * *10* riskiest files or functions in the Markdown summary, without any size limit.
+/// * *no history* of the runs.
+/// * *10* functions in the report of where to write tests next.
 #[derive(Debug)]
 pub struct WccRunner<'a>(Parameters<'a>);
 
@@ -100,6 +183,58 @@ impl<'a> WccRunner<'a> {
         self
     }
 
+    /// Sets the report that will be printed on the standard output.
+    pub fn output(mut self, output: Output) -> Self {
+        self.0.output = output;
+        self
+    }
+
+    /// Enables the computation of the metrics weighted by the number of hits
+    /// of each line.
+    pub fn hit_weighted(mut self, hit_weighted: bool) -> Self {
+        self.0.hit_weighted = hit_weighted;
+        self
+    }
+
+    /// Sets the complexity metrics, other than cyclomatic and cognitive,
+    /// against which the metrics will also be computed.
+    pub fn complexities(mut self, complexities: &[Complexity]) -> Self {
+        self.0.complexities = complexities
+            .iter()
+            .filter(|complexity| {
+                !matches!(complexity, Complexity::Cyclomatic | Complexity::Cognitive)
+            })
+            .copied()
+            .collect();
+        self.0.complexities.sort();
+        self.0.complexities.dedup();
+        self
+    }
+
+    /// Registers a custom metric, which is computed for files, functions
+    /// and project alongside the built-in ones.
+    ///
+    /// A custom metric crossing its threshold marks the code as complex,
+    /// and it can be used to sort the output through [`Sort::Custom`].
+    pub fn add_metric<M: Metric + 'static>(mut self, metric: M) -> Self {
+        self.0.custom_metrics.push(Box::new(metric));
+        self
+    }
+
+    /// Sets the file containing the linter diagnostics used as code smells
+    /// by the Skunk metric.
+    pub fn smells_file(mut self, smells_file: SmellsFile<&'a Path>) -> Self {
+        self.0.smells_file = Some(smells_file);
+        self
+    }
+
+    /// Sets the file containing the mutation testing results, enabling
+    /// the computation of the mutation-adjusted metrics.
+    pub fn mutations_file(mut self, mutations_file: MutationsFile<&'a Path>) -> Self {
+        self.0.mutations_file = Some(mutations_file);
+        self
+    }
+
     /// Sets the path of the json output.
     pub fn json_path(mut self, json_path: &'a Path) -> Self {
         self.0.json_path = Some(json_path);
@@ -112,6 +247,124 @@ impl<'a> WccRunner<'a> {
         self
     }
 
+    /// Sets a directory of minijinja templates overriding the built-in
+    /// html templates with the same name, e.g. `base.html` or `style.css`.
+    pub fn html_templates(mut self, html_templates: &'a Path) -> Self {
+        self.0.html_templates = Some(html_templates);
+        self
+    }
+
+    /// Writes the whole html output into a single `index.html` page, which
+    /// navigates between the project view and the pages of the files in the browser.
+    /// With the `offline-html` feature, the vendored assets are inlined in the page too.
+    pub fn html_single(mut self, html_single: bool) -> Self {
+        self.0.html_single = html_single;
+        self
+    }
+
+    /// Writes vendored copies of the html output assets into its directory,
+    /// so that the html output can be viewed offline.
+    #[cfg(feature = "offline-html")]
+    pub fn html_offline(mut self, html_offline: bool) -> Self {
+        self.0.html_offline = html_offline;
+        self
+    }
+
+    /// Sets the path of the SARIF output.
+    pub fn sarif_path(mut self, sarif_path: &'a Path) -> Self {
+        self.0.sarif_path = Some(sarif_path);
+        self
+    }
+
+    /// Sets the path of the CSV output.
+    pub fn csv_path(mut self, csv_path: &'a Path) -> Self {
+        self.0.csv_path = Some(csv_path);
+        self
+    }
+
+    /// Sets the path of the TSV output.
+    pub fn tsv_path(mut self, tsv_path: &'a Path) -> Self {
+        self.0.tsv_path = Some(tsv_path);
+        self
+    }
+
+    /// Sets the path of the JUnit XML report.
+    pub fn junit_path(mut self, junit_path: &'a Path) -> Self {
+        self.0.junit_path = Some(junit_path);
+        self
+    }
+
+    /// Sets the path of the Markdown summary.
+    pub fn markdown_path(mut self, markdown_path: &'a Path) -> Self {
+        self.0.markdown_path = Some(markdown_path);
+        self
+    }
+
+    /// Sets the path of the Code Climate issues, also used by GitLab code quality reports.
+    pub fn code_climate_path(mut self, code_climate_path: &'a Path) -> Self {
+        self.0.code_climate_path = Some(code_climate_path);
+        self
+    }
+
+    /// Sets the path of the SonarQube generic external issues.
+    pub fn sonar_issues_path(mut self, sonar_issues_path: &'a Path) -> Self {
+        self.0.sonar_issues_path = Some(sonar_issues_path);
+        self
+    }
+
+    /// Sets the path of the SonarQube generic test coverage.
+    pub fn sonar_coverage_path(mut self, sonar_coverage_path: &'a Path) -> Self {
+        self.0.sonar_coverage_path = Some(sonar_coverage_path);
+        self
+    }
+
+    /// Enables the SVG badges of the project Wcc and average CRAP computed
+    /// with the given complexity metric, written in the html output directory
+    /// or, when absent, next to the json output.
+    pub fn badges(mut self, complexity: Complexity) -> Self {
+        self.0.badges = Some(complexity);
+        self
+    }
+
+    /// Sets the number of riskiest files or functions listed in the Markdown summary.
+    pub fn markdown_top(mut self, markdown_top: usize) -> Self {
+        self.0.markdown_top = markdown_top;
+        self
+    }
+
+    /// Sets the maximum size in bytes of the Markdown summary, dropping
+    /// the riskiest files or functions which do not fit, together with
+    /// their table when none of them fits.
+    pub fn markdown_max_size(mut self, markdown_max_size: usize) -> Self {
+        self.0.markdown_max_size = Some(markdown_max_size);
+        self
+    }
+
+    /// Sets the number of functions listed in the report of where to write tests next.
+    pub fn priorities_top(mut self, priorities_top: usize) -> Self {
+        self.0.priorities_top = priorities_top;
+        self
+    }
+
+    /// Sets the path of the history file, a JSON Lines file to which each run
+    /// appends its project metrics and the metrics of its files.
+    pub fn history_path(mut self, history_path: &'a Path) -> Self {
+        self.0.history_path = Some(history_path);
+        self
+    }
+
+    /// Sets the commit hash of the analyzed code, stored in the history.
+    pub fn history_commit(mut self, history_commit: &'a str) -> Self {
+        self.0.history_commit = Some(history_commit);
+        self
+    }
+
+    /// Sets the label of the run, stored in the history.
+    pub fn history_label(mut self, history_label: &'a str) -> Self {
+        self.0.history_label = Some(history_label);
+        self
+    }
+
     /// Runs the weighted code coverage runner.
     pub fn run<P: AsRef<Path>>(
         self,
@@ -119,39 +372,106 @@ impl<'a> WccRunner<'a> {
         grcov_file: GrcovFile<P>,
     ) -> Result<WccOutput> {
         // Check if json_path is a json file.
-        if self.0.json_path.map_or(false, |path| {
+        if self.0.json_path.is_some_and(|path| {
             path.extension()
-                .map_or(false, |ext| ext.to_ascii_lowercase() != "json")
+                .is_some_and(|ext| !ext.eq_ignore_ascii_case("json"))
         }) {
             return Err(Error::OutputPath("Json output path must be a json file"));
         }
 
         // Check if html_path is a directory.
-        if self.0.html_path.map_or(false, |path| !path.is_dir()) {
+        if self.0.html_path.is_some_and(|path| !path.is_dir()) {
             return Err(Error::OutputPath("Html output path must be a directory"));
         }
 
+        // Check if html_templates is a directory.
+        if self.0.html_templates.is_some_and(|path| !path.is_dir()) {
+            return Err(Error::OutputPath("Html templates path must be a directory"));
+        }
+
+        // Check if the history commit and label have a history file.
+        if self.0.history_path.is_none()
+            && (self.0.history_commit.is_some() || self.0.history_label.is_some())
+        {
+            return Err(Error::Options(
+                "History commit and label need a history path".to_owned(),
+            ));
+        }
+
+        // Check if the report of where to write tests next has functions.
+        if self.0.output == Output::Priorities && self.0.mode == Mode::Files {
+            return Err(Error::Options(
+                "Priorities report needs the functions mode".to_owned(),
+            ));
+        }
+
+        // Check if the custom sort metric is registered.
+        if let Sort::Custom(name) = &self.0.sort_by {
+            if !self
+                .0
+                .custom_metrics
+                .iter()
+                .any(|metric| metric.name() == name)
+            {
+                return Err(Error::Options(format!(
+                    "Sort metric {name:?} is not a registered custom metric"
+                )));
+            }
+        }
+
+        // Check if badges have an output directory.
+        if self.0.badges.is_some() && self.badges_dir().is_none() {
+            return Err(Error::OutputPath("Badges need a json or html output path"));
+        }
+
+        // Check if the badges complexity metric is computed.
+        if self.0.badges.is_some_and(|complexity| {
+            !matches!(complexity, Complexity::Cyclomatic | Complexity::Cognitive)
+                && !self.0.complexities.contains(&complexity)
+        }) {
+            return Err(Error::Options(
+                "Badges complexity metric must be computed".to_owned(),
+            ));
+        }
+
         // Retrieve project files.
         let files = read_files(project_path)?;
 
         // Parse grcov file.
-        let grcov = self.get_grcov(project_path, grcov_file)?;
+        let grcov = self.get_grcov(project_path, &files, grcov_file)?;
+
+        // Parse linter diagnostics, if any.
+        let smells = self.get_smells(project_path)?;
+
+        // Parse mutation testing results, if any.
+        let mutations = self.get_mutations(project_path)?;
 
         // Retrieve project metrics concurrently.
         let wcc_output = Wcc {
             project_path,
             files: &files,
             mode: self.0.mode,
-            grcov,
-            metrics_thresholds: self.0.thresholds,
+            grcov: &grcov,
+            smells,
+            mutations,
+            metrics_config: MetricsConfig {
+                thresholds: self.0.thresholds,
+                hit_weighted: self.0.hit_weighted || self.0.sort_by.is_hit_weighted(),
+                mutation_adjusted: self.0.mutations_file.is_some(),
+                custom_metrics: &self.0.custom_metrics,
+                complexities: &self.0.complexities,
+            },
             files_metrics: Mutex::new(Vec::new()),
             ignored_files: Mutex::new(Vec::new()),
-            sort_by: self.0.sort_by,
+            sort_by: &self.0.sort_by,
         }
         .run(self.0.n_threads)?;
 
+        // Append the run to the history, if any.
+        let history = self.update_history(&wcc_output)?;
+
         // Write json and/or html output.
-        self.print(&wcc_output, project_path)?;
+        self.print(&wcc_output, project_path, &files, &grcov, history.as_ref())?;
 
         Ok(wcc_output)
     }
@@ -159,12 +479,15 @@ impl<'a> WccRunner<'a> {
     fn get_grcov<P: AsRef<Path>>(
         &self,
         project_path: &Path,
+        files: &[PathBuf],
         grcov_file: GrcovFile<P>,
     ) -> Result<Grcov> {
         let grcov = match grcov_file {
-            GrcovFile::Coveralls(coveralls_path) => {
-                Grcov::Coveralls(Coveralls::new(coveralls_path.as_ref(), project_path)?)
-            }
+            GrcovFile::Coveralls(coveralls_path) => Grcov::Coveralls(Coveralls::new(
+                coveralls_path.as_ref(),
+                project_path,
+                files,
+            )?),
             GrcovFile::Covdir(covdir_path) => {
                 Grcov::Covdir(Covdir::new(covdir_path.as_ref(), project_path)?)
             }
@@ -173,7 +496,54 @@ impl<'a> WccRunner<'a> {
         Ok(grcov)
     }
 
-    fn print(&self, wcc_output: &WccOutput, project_path: &Path) -> Result<()> {
+    fn get_smells(&self, project_path: &Path) -> Result<Smells> {
+        let smells = match self.0.smells_file {
+            Some(SmellsFile::Cargo(cargo_path)) => Smells::from_cargo(cargo_path, project_path)?,
+            Some(SmellsFile::Sarif(sarif_path)) => Smells::from_sarif(sarif_path, project_path)?,
+            None => Smells::default(),
+        };
+
+        Ok(smells)
+    }
+
+    fn get_mutations(&self, project_path: &Path) -> Result<Mutations> {
+        let mutations = match self.0.mutations_file {
+            Some(MutationsFile::CargoMutants(outcomes_path)) => {
+                Mutations::from_cargo_mutants(outcomes_path, project_path)?
+            }
+            Some(MutationsFile::Stryker(report_path)) => {
+                Mutations::from_stryker(report_path, project_path)?
+            }
+            None => Mutations::default(),
+        };
+
+        Ok(mutations)
+    }
+
+    fn update_history(&self, wcc_output: &WccOutput) -> Result<Option<History>> {
+        let Some(history_path) = self.0.history_path else {
+            return Ok(None);
+        };
+
+        let entry = HistoryEntry::new(
+            wcc_output,
+            self.0.mode,
+            self.0.history_commit,
+            self.0.history_label,
+        );
+        History::append(history_path, &entry)?;
+
+        History::from_path(history_path).map(Some)
+    }
+
+    fn print(
+        &self,
+        wcc_output: &WccOutput,
+        project_path: &Path,
+        files: &[PathBuf],
+        grcov: &Grcov,
+        history: Option<&History>,
+    ) -> Result<()> {
         if let Some(json_path) = self.0.json_path {
             JsonPrinter {
                 project_path,
@@ -189,14 +559,136 @@ impl<'a> WccRunner<'a> {
             HtmlPrinter {
                 wcc_output,
                 output_path: html_path,
+                project_path,
+                files,
+                grcov,
+                mode: self.0.mode,
+                thresholds: self.0.thresholds,
+                templates_dir: self.0.html_templates,
+                history,
+                single: self.0.html_single,
+                #[cfg(feature = "offline-html")]
+                offline: self.0.html_offline,
+            }
+            .print()?;
+        }
+
+        for (path, delimiter) in [
+            (self.0.csv_path, CSV_DELIMITER),
+            (self.0.tsv_path, TSV_DELIMITER),
+        ] {
+            if let Some(output_path) = path {
+                CsvPrinter {
+                    wcc_output,
+                    output_path,
+                    mode: self.0.mode,
+                    delimiter,
+                }
+                .print()?;
+            }
+        }
+
+        if let Some(junit_path) = self.0.junit_path {
+            JunitPrinter {
+                wcc_output,
+                output_path: junit_path,
+                mode: self.0.mode,
+                thresholds: self.0.thresholds,
+            }
+            .print()?;
+        }
+
+        match self.0.output {
+            Output::None => {}
+            Output::Table => TablePrinter {
+                wcc_output,
+                mode: self.0.mode,
+                sort_by: &self.0.sort_by,
+                thresholds: self.0.thresholds,
+            }
+            .print()?,
+            Output::Priorities => PrioritiesPrinter {
+                wcc_output,
+                top: self.0.priorities_top,
+                thresholds: self.0.thresholds,
+            }
+            .print()?,
+        }
+
+        if let Some(markdown_path) = self.0.markdown_path {
+            MarkdownPrinter {
+                wcc_output,
+                output_path: markdown_path,
+                mode: self.0.mode,
+                sort_by: &self.0.sort_by,
+                top: self.0.markdown_top,
+                max_size: self.0.markdown_max_size,
+            }
+            .print()?;
+        }
+
+        if let Some(sarif_path) = self.0.sarif_path {
+            SarifPrinter {
+                wcc_output,
+                output_path: sarif_path,
                 mode: self.0.mode,
                 thresholds: self.0.thresholds,
             }
             .print()?;
         }
 
+        if let Some(code_climate_path) = self.0.code_climate_path {
+            CodeClimatePrinter {
+                wcc_output,
+                output_path: code_climate_path,
+                mode: self.0.mode,
+                thresholds: self.0.thresholds,
+            }
+            .print()?;
+        }
+
+        if let Some(sonar_issues_path) = self.0.sonar_issues_path {
+            SonarIssuesPrinter {
+                wcc_output,
+                output_path: sonar_issues_path,
+                mode: self.0.mode,
+                thresholds: self.0.thresholds,
+            }
+            .print()?;
+        }
+
+        if let Some(sonar_coverage_path) = self.0.sonar_coverage_path {
+            SonarCoveragePrinter {
+                wcc_output,
+                output_path: sonar_coverage_path,
+                project_path,
+                files,
+                grcov,
+            }
+            .print()?;
+        }
+
+        if let (Some(complexity), Some(output_dir)) = (self.0.badges, self.badges_dir()) {
+            BadgePrinter {
+                wcc_output,
+                output_dir,
+                complexity,
+                thresholds: self.0.thresholds,
+            }
+            .print()?;
+        }
+
         Ok(())
     }
+
+    // Directory of the badges: the html output directory or,
+    // when absent, the directory of the json output.
+    #[inline]
+    fn badges_dir(&self) -> Option<&'a Path> {
+        self.0
+            .html_path
+            .or_else(|| self.0.json_path.and_then(Path::parent))
+    }
 }
 
 impl Default for WccRunner<'_> {
@@ -219,6 +711,16 @@ fn valid_extension(ext: &OsStr) -> bool {
         || ext == "jsm"
 }
 
+// Resolves a file path reported by an external tool against the project path,
+// using the same format as the project source files.
+//
+// Absolute paths and `file://` uris are kept as they are by `join`.
+#[inline]
+pub(crate) fn resolve_path(project_path: &Path, file: &str) -> PathBuf {
+    let file = project_path.join(file.strip_prefix("file://").unwrap_or(file));
+    PathBuf::from(file.to_string_lossy().replace('\\', "/"))
+}
+
 // Returns the list of project source files.
 #[inline]
 fn read_files(project_path: &Path) -> Result<Vec<PathBuf>> {
@@ -232,7 +734,7 @@ fn read_files(project_path: &Path) -> Result<Vec<PathBuf>> {
                 if ancestor
                     .file_name()
                     .and_then(|name| name.to_str())
-                    .map_or(false, |dir| dir.contains("target"))
+                    .is_some_and(|dir| dir.contains("target"))
                 {
                     continue 'outer;
                 }
@@ -286,10 +788,9 @@ impl FromStr for GrcovFormat {
         match grcov_format {
             "coveralls" => Ok(Self::Coveralls),
             "covdir" => Ok(Self::Covdir),
-            _ => Err(std::io::Error::new(
-                ErrorKind::Other,
-                format!("{grcov_format:?} is not a supported grcov format."),
-            )),
+            _ => Err(std::io::Error::other(format!(
+                "{grcov_format:?} is not a supported grcov format."
+            ))),
         }
     }
 }
@@ -303,23 +804,240 @@ pub enum GrcovFile<P: AsRef<Path>> {
     Covdir(P),
 }
 
+/// Availabe linter diagnostics file formats.
+#[derive(Debug, Clone)]
+pub enum SmellsFormat {
+    /// Json messages emitted by `cargo clippy --message-format=json`.
+    Cargo,
+    /// SARIF log.
+    Sarif,
+}
+
+impl SmellsFormat {
+    /// All `SmellsFormat` options.
+    pub const fn all() -> &'static [&'static str] {
+        &["cargo", "sarif"]
+    }
+}
+
+impl fmt::Display for SmellsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::Cargo => "cargo",
+            Self::Sarif => "sarif",
+        };
+        s.fmt(f)
+    }
+}
+
+impl FromStr for SmellsFormat {
+    type Err = std::io::Error;
+
+    fn from_str(smells_format: &str) -> std::result::Result<Self, Self::Err> {
+        match smells_format {
+            "cargo" => Ok(Self::Cargo),
+            "sarif" => Ok(Self::Sarif),
+            _ => Err(std::io::Error::other(format!(
+                "{smells_format:?} is not a supported smells format."
+            ))),
+        }
+    }
+}
+
+/// Linter diagnostics file passed as input argument.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SmellsFile<P: AsRef<Path>> {
+    /// Cargo json messages.
+    Cargo(P),
+    /// SARIF log.
+    Sarif(P),
+}
+
+/// Availabe mutation testing results formats.
+#[derive(Debug, Clone)]
+pub enum MutationsFormat {
+    /// `outcomes.json` written by cargo-mutants.
+    CargoMutants,
+    /// Mutation testing report, as written by Stryker or PIT.
+    Stryker,
+}
+
+impl MutationsFormat {
+    /// All `MutationsFormat` options.
+    pub const fn all() -> &'static [&'static str] {
+        &["cargo-mutants", "stryker"]
+    }
+}
+
+impl fmt::Display for MutationsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::CargoMutants => "cargo-mutants",
+            Self::Stryker => "stryker",
+        };
+        s.fmt(f)
+    }
+}
+
+impl FromStr for MutationsFormat {
+    type Err = std::io::Error;
+
+    fn from_str(mutations_format: &str) -> std::result::Result<Self, Self::Err> {
+        match mutations_format {
+            "cargo-mutants" => Ok(Self::CargoMutants),
+            "stryker" => Ok(Self::Stryker),
+            _ => Err(std::io::Error::other(format!(
+                "{mutations_format:?} is not a supported mutations format."
+            ))),
+        }
+    }
+}
+
+/// Mutation testing results file passed as input argument.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MutationsFile<P: AsRef<Path>> {
+    /// cargo-mutants outcomes.
+    CargoMutants(P),
+    /// Stryker mutation testing report.
+    Stryker(P),
+}
+
 /// Complexity Metrics.
-#[derive(Copy, Debug, Default, Clone, PartialEq, Eq, Hash, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(
+    Copy,
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
 pub enum Complexity {
     /// Cyclomatic metric.
     #[default]
     Cyclomatic,
     /// Cognitive metric.
     Cognitive,
+    /// Halstead effort metric.
+    HalsteadEffort,
+    /// Halstead difficulty metric.
+    HalsteadDifficulty,
+    /// Maintainability Index metric, expressed as `100 - MI`
+    /// using the Visual Studio variant, so that higher values mean more complex code.
+    MaintainabilityIndex,
+    /// Number of exit points metric.
+    Exits,
+    /// Number of arguments metric.
+    Args,
+}
+
+impl Complexity {
+    // All `Complexity` variants, in the same order used to index them.
+    pub(crate) const VARIANTS: [Complexity; 7] = [
+        Self::Cyclomatic,
+        Self::Cognitive,
+        Self::HalsteadEffort,
+        Self::HalsteadDifficulty,
+        Self::MaintainabilityIndex,
+        Self::Exits,
+        Self::Args,
+    ];
+
+    /// All `Complexity` options.
+    pub const fn all() -> &'static [&'static str] {
+        &[
+            "cyclomatic",
+            "cognitive",
+            "halstead-effort",
+            "halstead-difficulty",
+            "maintainability-index",
+            "exits",
+            "args",
+        ]
+    }
+
+    // Index of the complexity inside `Complexity::VARIANTS`.
+    #[inline]
+    pub(crate) const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::Cyclomatic => "cyclomatic",
+            Self::Cognitive => "cognitive",
+            Self::HalsteadEffort => "halstead-effort",
+            Self::HalsteadDifficulty => "halstead-difficulty",
+            Self::MaintainabilityIndex => "maintainability-index",
+            Self::Exits => "exits",
+            Self::Args => "args",
+        };
+        s.fmt(f)
+    }
+}
+
+impl FromStr for Complexity {
+    type Err = std::io::Error;
+
+    fn from_str(complexity: &str) -> std::result::Result<Self, Self::Err> {
+        match complexity {
+            "cyclomatic" => Ok(Self::Cyclomatic),
+            "cognitive" => Ok(Self::Cognitive),
+            "halstead-effort" => Ok(Self::HalsteadEffort),
+            "halstead-difficulty" => Ok(Self::HalsteadDifficulty),
+            "maintainability-index" => Ok(Self::MaintainabilityIndex),
+            "exits" => Ok(Self::Exits),
+            "args" => Ok(Self::Args),
+            _ => Err(std::io::Error::other(format!(
+                "{complexity:?} is not a supported complexity metric."
+            ))),
+        }
+    }
 }
 
 /// Thresholds.
+///
+/// Besides Wcc, they contain the maximum complexity of a code space for each
+/// complexity metric, used to compute the CRAP and Skunk thresholds.
 #[derive(Debug, Clone, Copy, Serialize)]
 pub struct Thresholds {
     wcc: f64,
     cyclomatic_complexity: f64,
     cognitive_complexity: f64,
+    // Complexity thresholds of the additional complexity metrics, indexed by
+    // `Complexity::index`, where the cyclomatic and cognitive ones are unused.
+    #[serde(skip)]
+    complexities: Complexities,
+}
+
+impl Thresholds {
+    /// Sets the maximum complexity of a code space for the given complexity metric.
+    pub fn complexity(mut self, complexity: Complexity, threshold: f64) -> Self {
+        match complexity {
+            Complexity::Cyclomatic => self.cyclomatic_complexity = threshold,
+            Complexity::Cognitive => self.cognitive_complexity = threshold,
+            _ => self.complexities[complexity.index()] = threshold,
+        }
+        self
+    }
+
+    // Maximum complexity of a code space for the given complexity metric.
+    #[inline]
+    pub(crate) fn get_complexity(&self, complexity: Complexity) -> f64 {
+        match complexity {
+            Complexity::Cyclomatic => self.cyclomatic_complexity,
+            Complexity::Cognitive => self.cognitive_complexity,
+            _ => self.complexities[complexity.index()],
+        }
+    }
 }
 
 impl fmt::Display for Thresholds {
@@ -328,7 +1046,16 @@ impl fmt::Display for Thresholds {
             f,
             "{},{},{}",
             self.wcc, self.cyclomatic_complexity, self.cognitive_complexity
-        )
+        )?;
+        // Only the additional complexity thresholds which differ from the default ones.
+        for complexity in &Complexity::VARIANTS[2..] {
+            let threshold = self.get_complexity(*complexity);
+            if threshold != complexity_threshold(*complexity) {
+                write!(f, ",{complexity}={threshold}")?;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -338,6 +1065,7 @@ impl Default for Thresholds {
             wcc: 60.0,
             cyclomatic_complexity: 10.0,
             cognitive_complexity: 10.0,
+            complexities: Complexity::VARIANTS.map(complexity_threshold),
         }
     }
 }
@@ -346,33 +1074,45 @@ impl FromStr for Thresholds {
     type Err = std::io::Error;
 
     fn from_str(thresholds: &str) -> std::result::Result<Self, Self::Err> {
-        let mut iter = thresholds.split(',').filter_map(|s| s.parse::<f64>().ok());
-
-        Ok(Self {
-            wcc: iter.next().ok_or(std::io::Error::new(
-                ErrorKind::InvalidInput,
-                format!("Missing or invalid wcc in thresholds: {}", thresholds),
-            ))?,
-            cyclomatic_complexity: iter.next().ok_or(std::io::Error::new(
-                ErrorKind::InvalidInput,
-                format!(
-                    "Missing or invalid cyclomatic complexity in thresholds: {}",
-                    thresholds
-                ),
-            ))?,
-            cognitive_complexity: iter.next().ok_or(std::io::Error::new(
+        let invalid = |name: &str| {
+            std::io::Error::new(
                 ErrorKind::InvalidInput,
-                format!(
-                    "Missing or invalid cognitive complexity in thresholds: {}",
-                    thresholds
-                ),
-            ))?,
-        })
+                format!("Missing or invalid {name} in thresholds: {thresholds}"),
+            )
+        };
+        let mut values = thresholds.split(',').map(str::trim);
+        let mut next_value = |name| {
+            values
+                .next()
+                .and_then(|value| value.parse::<f64>().ok())
+                .ok_or_else(|| invalid(name))
+        };
+
+        let mut result = Self {
+            wcc: next_value("wcc")?,
+            cyclomatic_complexity: next_value("cyclomatic complexity")?,
+            cognitive_complexity: next_value("cognitive complexity")?,
+            ..Self::default()
+        };
+        // The additional complexity thresholds follow as `name=value` pairs.
+        for pair in values {
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| invalid("complexity threshold"))?;
+            let complexity = name.trim().parse()?;
+            let value = value
+                .trim()
+                .parse()
+                .map_err(|_| invalid(&format!("{name} complexity")))?;
+            result = result.complexity(complexity, value);
+        }
+
+        Ok(result)
     }
 }
 
 /// Mode.
-#[derive(Copy, Debug, Default, Clone, PartialEq, Eq, Hash, Serialize)]
+#[derive(Copy, Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum Mode {
     /// Files Mode.
     #[default]
@@ -410,16 +1150,65 @@ impl FromStr for Mode {
         match mode {
             "files" => Ok(Mode::Files),
             "functions" => Ok(Mode::Functions),
-            _ => Err(std::io::Error::new(
-                ErrorKind::Other,
-                format!("{mode:?} is not a supported mode."),
-            )),
+            _ => Err(std::io::Error::other(format!(
+                "{mode:?} is not a supported mode."
+            ))),
         }
     }
 }
 
-/// Sort.
+/// Report printed on the standard output.
 #[derive(Copy, Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum Output {
+    /// No report.
+    #[default]
+    None,
+    /// Colored table of the project, files or functions metrics.
+    Table,
+    /// Functions where covering the uncovered lines improves the metrics the most.
+    Priorities,
+}
+
+impl Output {
+    /// All `Output` options.
+    pub const fn all() -> &'static [&'static str] {
+        &["none", "table", "priorities"]
+    }
+
+    /// Default `Output` option.
+    pub const fn default_value() -> &'static str {
+        "none"
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::None => "none",
+            Self::Table => "table",
+            Self::Priorities => "priorities",
+        };
+        s.fmt(f)
+    }
+}
+
+impl FromStr for Output {
+    type Err = std::io::Error;
+
+    fn from_str(output: &str) -> std::result::Result<Self, Self::Err> {
+        match output {
+            "none" => Ok(Output::None),
+            "table" => Ok(Output::Table),
+            "priorities" => Ok(Output::Priorities),
+            _ => Err(std::io::Error::other(format!(
+                "{output:?} is not a supported output."
+            ))),
+        }
+    }
+}
+
+/// Sort.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub enum Sort {
     /// Wcc.
     #[default]
@@ -428,12 +1217,27 @@ pub enum Sort {
     Crap,
     /// Skunk.
     Skunk,
+    /// Hit-weighted Wcc.
+    HitWcc,
+    /// Hit-weighted Crap.
+    HitCrap,
+    /// Hit-weighted Skunk.
+    HitSkunk,
+    /// Custom metric registered with [`WccRunner::add_metric`], identified by its name.
+    ///
+    /// Running with a name which matches no registered metric fails.
+    Custom(String),
 }
 
 impl Sort {
     /// All `Sort` options.
     pub const fn all() -> &'static [&'static str] {
-        &["wcc", "crap", "skunk"]
+        &["wcc", "crap", "skunk", "hit-wcc", "hit-crap", "hit-skunk"]
+    }
+
+    /// Whether the `Sort` option is a hit-weighted metric.
+    pub const fn is_hit_weighted(&self) -> bool {
+        matches!(self, Self::HitWcc | Self::HitCrap | Self::HitSkunk)
     }
 
     /// Default `Sort` option.
@@ -448,6 +1252,10 @@ impl fmt::Display for Sort {
             Self::Wcc => "wcc",
             Self::Crap => "crap",
             Self::Skunk => "skunk",
+            Self::HitWcc => "hit-wcc",
+            Self::HitCrap => "hit-crap",
+            Self::HitSkunk => "hit-skunk",
+            Self::Custom(name) => name.as_str(),
         };
         s.fmt(f)
     }
@@ -461,10 +1269,12 @@ impl FromStr for Sort {
             "wcc" => Ok(Sort::Wcc),
             "crap" => Ok(Sort::Crap),
             "skunk" => Ok(Sort::Skunk),
-            _ => Err(std::io::Error::new(
-                ErrorKind::Other,
-                format!("{sort:?} is not a supported metric."),
-            )),
+            "hit-wcc" => Ok(Sort::HitWcc),
+            "hit-crap" => Ok(Sort::HitCrap),
+            "hit-skunk" => Ok(Sort::HitSkunk),
+            _ => Err(std::io::Error::other(format!(
+                "{sort:?} is not a supported metric."
+            ))),
         }
     }
 }
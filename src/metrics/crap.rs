@@ -1,4 +1,6 @@
-use super::round_sd;
+use crate::Complexity;
+
+use super::{round_sd, Metric, MetricInput, MetricsThresholds};
 
 // Computes the CRAP score given coverage and complexity of a FuncSpace.
 // https://testing.googleblog.com/2011/02/this-code-is-crap.html#:~:text=CRAP%20is%20short%20for%20Change,partner%20in%20crime%20Bob%20Evans
@@ -8,3 +10,21 @@ pub(crate) fn crap(coverage: f64, complexity: f64) -> f64 {
 
     round_sd(crap)
 }
+
+// CRAP built-in metric.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CrapMetric(pub(crate) MetricsThresholds);
+
+impl Metric for CrapMetric {
+    fn name(&self) -> &str {
+        "crap"
+    }
+
+    fn score(&self, input: &MetricInput) -> f64 {
+        crap(input.coverage(), input.complexity)
+    }
+
+    fn exceeds_threshold(&self, score: f64, complexity: Complexity) -> bool {
+        score > self.0.crap(complexity)
+    }
+}
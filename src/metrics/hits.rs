@@ -0,0 +1,17 @@
+// Number of executions after which a line is considered thoroughly exercised.
+pub(crate) const HITS_SATURATION: f64 = 10.0;
+
+// Weighs a line by its execution frequency, returning a value in [0, 1].
+//
+// A line which has never been executed weighs 0, while the weight grows
+// logarithmically with the number of hits, reaching 1 at `HITS_SATURATION`.
+// In this way, lines executed only once by an incidental test count
+// less than lines exercised repeatedly.
+#[inline]
+pub(crate) fn hit_weight(hits: i32) -> f64 {
+    if hits <= 0 {
+        return 0.0;
+    }
+
+    ((hits as f64).ln_1p() / HITS_SATURATION.ln_1p()).min(1.0)
+}
@@ -1,8 +1,9 @@
 pub(crate) mod crap;
+pub(crate) mod hits;
 pub(crate) mod skunk;
 pub(crate) mod wcc;
 
-use std::path::Path;
+use std::{fmt, path::Path};
 
 use rust_code_analysis::{get_function_spaces, guess_language, read_file, FuncSpace};
 use serde::Serialize;
@@ -13,7 +14,14 @@ use crate::{
     Complexity, Thresholds,
 };
 
+use self::{crap::CrapMetric, skunk::SkunkMetric, wcc::WccMetric};
+
 const COVERAGE_THRESHOLD: f64 = 0.6;
+// Values found inverting the metric formulas are rounded up to a tenth.
+const INVERSION_PRECISION: f64 = 10.0;
+
+// Values of all the complexity metrics, indexed by `Complexity::index`.
+pub(crate) type Complexities = [f64; Complexity::VARIANTS.len()];
 
 #[derive(Debug, Serialize, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +31,9 @@ pub(crate) struct MetricsThresholds {
     crap_cognitive: f64,
     skunk_cyclomatic: f64,
     skunk_cognitive: f64,
+    // Complexity thresholds of the additional complexity metrics.
+    #[serde(skip)]
+    complexities: Complexities,
 }
 
 impl From<Thresholds> for MetricsThresholds {
@@ -32,8 +43,9 @@ impl From<Thresholds> for MetricsThresholds {
             wcc: value.wcc,
             crap_cyclomatic: crap::crap(COVERAGE_THRESHOLD, value.cyclomatic_complexity),
             crap_cognitive: crap::crap(COVERAGE_THRESHOLD, value.cognitive_complexity),
-            skunk_cyclomatic: skunk::skunk(COVERAGE_THRESHOLD, value.cyclomatic_complexity),
-            skunk_cognitive: skunk::skunk(COVERAGE_THRESHOLD, value.cognitive_complexity),
+            skunk_cyclomatic: skunk::skunk(COVERAGE_THRESHOLD, value.cyclomatic_complexity, 0.0),
+            skunk_cognitive: skunk::skunk(COVERAGE_THRESHOLD, value.cognitive_complexity, 0.0),
+            complexities: Complexity::VARIANTS.map(|complexity| value.get_complexity(complexity)),
         }
     }
 }
@@ -47,25 +59,154 @@ impl Default for MetricsThresholds {
             crap_cognitive: 16.4,
             skunk_cyclomatic: 16.66,
             skunk_cognitive: 16.66,
+            complexities: Complexity::VARIANTS.map(complexity_threshold),
         }
     }
 }
 
 impl MetricsThresholds {
+    // Wcc threshold, the same for all the complexity metrics.
     #[inline]
-    pub(crate) fn is_complex(
-        &self,
-        wcc: f64,
-        crap: f64,
-        skunk: f64,
-        complexity: Complexity,
-    ) -> bool {
-        let (crap_threshold, skunk_threshold) = match complexity {
-            Complexity::Cyclomatic => (self.crap_cyclomatic, self.skunk_cyclomatic),
-            Complexity::Cognitive => (self.crap_cognitive, self.skunk_cognitive),
-        };
+    pub(crate) const fn wcc(&self) -> f64 {
+        self.wcc
+    }
+
+    // CRAP threshold for the given complexity metric.
+    #[inline]
+    pub(crate) fn crap(&self, complexity: Complexity) -> f64 {
+        match complexity {
+            Complexity::Cyclomatic => self.crap_cyclomatic,
+            Complexity::Cognitive => self.crap_cognitive,
+            _ => crap::crap(COVERAGE_THRESHOLD, self.complexities[complexity.index()]),
+        }
+    }
+
+    // Skunk threshold for the given complexity metric.
+    #[inline]
+    pub(crate) fn skunk(&self, complexity: Complexity) -> f64 {
+        match complexity {
+            Complexity::Cyclomatic => self.skunk_cyclomatic,
+            Complexity::Cognitive => self.skunk_cognitive,
+            _ => skunk::skunk(
+                COVERAGE_THRESHOLD,
+                self.complexities[complexity.index()],
+                0.0,
+            ),
+        }
+    }
+}
+
+// Default maximum complexity of a code space for each complexity metric,
+// which can be overridden through `Thresholds` to compute CRAP and Skunk thresholds.
+#[inline]
+pub(crate) const fn complexity_threshold(complexity: Complexity) -> f64 {
+    match complexity {
+        Complexity::Cyclomatic | Complexity::Cognitive => 10.0,
+        Complexity::HalsteadEffort => 10000.0,
+        Complexity::HalsteadDifficulty => 20.0,
+        Complexity::MaintainabilityIndex => 80.0,
+        Complexity::Exits => 4.0,
+        Complexity::Args => 5.0,
+    }
+}
+
+/// Input of a metric.
+///
+/// For a function, the values refer to its code space, while for
+/// files and project they are aggregated over all their code spaces.
+#[derive(Debug, Clone, Copy)]
+pub struct MetricInput {
+    /// Complexity metric the input refers to.
+    pub complexity_type: Complexity,
+    /// Complexity, averaged over the code spaces for files and project.
+    pub complexity: f64,
+    /// Physical lines of code.
+    pub ploc: f64,
+    /// Covered lines.
+    pub covered_lines: f64,
+    /// Covered lines weighted by the number of hits of each line.
+    pub hit_covered_lines: f64,
+    /// Total number of hits of the lines.
+    pub hits: f64,
+    /// Covered lines of the code spaces whose complexity
+    /// does not exceed the Wcc complexity threshold.
+    pub wcc_covered_lines: f64,
+    /// Hit-weighted covered lines of the code spaces whose complexity
+    /// does not exceed the Wcc complexity threshold.
+    pub wcc_hit_covered_lines: f64,
+    /// Code smells, averaged over the code spaces for files and project.
+    pub smells: f64,
+    /// Covered lines discounted by the mutation score of each code space.
+    pub mutation_covered_lines: f64,
+    /// Mutation-adjusted covered lines of the code spaces whose complexity
+    /// does not exceed the Wcc complexity threshold.
+    pub wcc_mutation_covered_lines: f64,
+}
+
+impl MetricInput {
+    /// Coverage in the [0, 1] range.
+    #[inline]
+    pub fn coverage(&self) -> f64 {
+        self.covered_lines / self.ploc
+    }
+
+    // Replaces covered lines with hit-weighted covered lines.
+    #[inline]
+    pub(crate) fn hit_weighted(mut self) -> Self {
+        self.covered_lines = self.hit_covered_lines;
+        self.wcc_covered_lines = self.wcc_hit_covered_lines;
+        self
+    }
+
+    // Replaces covered lines with mutation-adjusted covered lines.
+    #[inline]
+    pub(crate) fn mutation_adjusted(mut self) -> Self {
+        self.covered_lines = self.mutation_covered_lines;
+        self.wcc_covered_lines = self.wcc_mutation_covered_lines;
+        self
+    }
+}
+
+/// A metric combining coverage and complexity of a code into a score.
+///
+/// Wcc, CRAP and Skunk are built-in implementations of this trait,
+/// while custom metrics can be registered through [`crate::WccRunner::add_metric`].
+pub trait Metric: fmt::Debug + Send + Sync {
+    /// Name of the metric, used to identify it in the output and for sorting.
+    fn name(&self) -> &str;
+
+    /// Computes the score of the metric.
+    fn score(&self, input: &MetricInput) -> f64;
 
-        wcc < self.wcc || crap > crap_threshold || skunk > skunk_threshold
+    /// Whether the score crosses the threshold of the metric
+    /// for the given complexity metric.
+    fn exceeds_threshold(&self, score: f64, complexity: Complexity) -> bool;
+}
+
+// Configuration shared by all the metrics computations.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MetricsConfig<'a> {
+    pub(crate) thresholds: MetricsThresholds,
+    pub(crate) hit_weighted: bool,
+    pub(crate) mutation_adjusted: bool,
+    pub(crate) custom_metrics: &'a [Box<dyn Metric>],
+    pub(crate) complexities: &'a [Complexity],
+}
+
+impl MetricsConfig<'_> {
+    #[inline]
+    pub(crate) const fn wcc(&self) -> WccMetric {
+        WccMetric(self.thresholds)
+    }
+
+    #[inline]
+    pub(crate) const fn crap(&self) -> CrapMetric {
+        CrapMetric(self.thresholds)
+    }
+
+    #[inline]
+    pub(crate) const fn skunk(&self) -> SkunkMetric {
+        SkunkMetric(self.thresholds)
     }
 }
 
@@ -84,6 +225,31 @@ pub(crate) fn get_root(path: &Path) -> Result<FuncSpace> {
     Ok(root)
 }
 
+// Retrieve the values of all the complexity metrics of a FuncSpace.
+#[inline]
+pub(crate) fn get_complexities(space: &FuncSpace) -> Complexities {
+    let metrics = &space.metrics;
+
+    Complexity::VARIANTS.map(|complexity| {
+        let value = match complexity {
+            Complexity::Cyclomatic => metrics.cyclomatic.cyclomatic_sum(),
+            Complexity::Cognitive => metrics.cognitive.cognitive_sum(),
+            Complexity::HalsteadEffort => metrics.halstead.effort(),
+            Complexity::HalsteadDifficulty => metrics.halstead.difficulty(),
+            Complexity::MaintainabilityIndex => (100.0 - metrics.mi.mi_visual_studio()).max(0.0),
+            Complexity::Exits => metrics.nexits.exit_sum(),
+            Complexity::Args => metrics.nargs.nargs_total(),
+        };
+
+        // Halstead and MI are not defined for spaces without operators and operands.
+        if value.is_finite() {
+            value
+        } else {
+            0.0
+        }
+    })
+}
+
 #[inline]
 pub(crate) fn get_line_space(root: &FuncSpace, line: usize) -> &FuncSpace {
     let mut line_space = root;
@@ -100,6 +266,24 @@ pub(crate) fn get_line_space(root: &FuncSpace, line: usize) -> &FuncSpace {
     line_space
 }
 
+// Retrieve the FuncSpaces containing the given 1-based line, from the innermost
+// to the root, including the lines of their signatures and closing delimiters.
+#[inline]
+pub(crate) fn get_enclosing_spaces(root: &FuncSpace, line: usize) -> Vec<&FuncSpace> {
+    let mut enclosing_spaces = vec![root];
+    while let Some(space) = enclosing_spaces.last().and_then(|space| {
+        space
+            .spaces
+            .iter()
+            .find(|s| (s.start_line..=s.end_line).contains(&line))
+    }) {
+        enclosing_spaces.push(space);
+    }
+    enclosing_spaces.reverse();
+
+    enclosing_spaces
+}
+
 #[inline]
 pub(crate) fn get_space_name(space: &FuncSpace) -> Option<String> {
     let name = format!(
@@ -112,6 +296,41 @@ pub(crate) fn get_space_name(space: &FuncSpace) -> Option<String> {
     Some(name)
 }
 
+// Smallest value of `low..=high` which passes, if any, given that
+// the values after a passing one pass too.
+pub(crate) fn first_passing(
+    mut low: usize,
+    mut high: usize,
+    passes: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    if low > high || !passes(high) {
+        return None;
+    }
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if passes(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    Some(low)
+}
+
+// Inverts a metric formula, finding the smallest value of `low..=high`,
+// rounded up to a tenth, which passes, given that the values after a
+// passing one pass too.
+pub(crate) fn invert(low: f64, high: f64, passes: impl Fn(f64) -> bool) -> Option<f64> {
+    let value = |step: usize| step as f64 / INVERSION_PRECISION;
+    first_passing(
+        (low * INVERSION_PRECISION).ceil() as usize,
+        (high * INVERSION_PRECISION).round() as usize,
+        |step| passes(value(step)),
+    )
+    .map(value)
+}
+
 // Round f64 to first decimal.
 #[inline]
 pub(crate) fn round_sd(x: f64) -> f64 {
@@ -1,13 +1,37 @@
-use super::round_sd;
+use crate::Complexity;
+
+use super::{round_sd, Metric, MetricInput, MetricsThresholds};
 
 const COMPLEXITY_FACTOR: f64 = 60.0;
+// Cost added by each code smell, as if it were a complexity point.
+const SMELL_PENALTY: f64 = 1.0;
 
-// Computes the Skunk score given coverage and complexity of a FuncSpace.
-// This implementation ignores code smells.
+// Computes the Skunk score given coverage, complexity and code smells of a FuncSpace.
+// Code smells increase the cost of the space, so they are penalized
+// even when the space is fully covered.
 // https://www.fastruby.io/blog/code-quality/intruducing-skunk-stink-score-calculator.html
 #[inline]
-pub(crate) fn skunk(coverage: f64, complexity: f64) -> f64 {
-    let skunk = ((complexity / COMPLEXITY_FACTOR) * (100.0 - (coverage * 100.0))) + complexity;
+pub(crate) fn skunk(coverage: f64, complexity: f64, smells: f64) -> f64 {
+    let cost = complexity + smells * SMELL_PENALTY;
+    let skunk = ((cost / COMPLEXITY_FACTOR) * (100.0 - (coverage * 100.0))) + cost;
 
     round_sd(skunk)
 }
+
+// Skunk built-in metric.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SkunkMetric(pub(crate) MetricsThresholds);
+
+impl Metric for SkunkMetric {
+    fn name(&self) -> &str {
+        "skunk"
+    }
+
+    fn score(&self, input: &MetricInput) -> f64 {
+        skunk(input.coverage(), input.complexity, input.smells)
+    }
+
+    fn exceeds_threshold(&self, score: f64, complexity: Complexity) -> bool {
+        score > self.0.skunk(complexity)
+    }
+}
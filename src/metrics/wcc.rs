@@ -1,17 +1,35 @@
-use super::round_sd;
+use crate::Complexity;
 
-pub(crate) const WCC_COMPLEXITY_THRESHOLD: f64 = 15.;
+use super::{complexity_threshold, round_sd, Metric, MetricInput, MetricsThresholds};
 
-#[inline]
-pub(crate) fn wcc_function(complexity: f64, wcc_coverage: f64, ploc: f64) -> f64 {
-    if complexity > WCC_COMPLEXITY_THRESHOLD {
-        return 0.0;
-    }
+// Ratio between the complexity above which a code space does not contribute
+// to Wcc and the default complexity threshold, i.e. 15 for cyclomatic and cognitive.
+const WCC_COMPLEXITY_FACTOR: f64 = 1.5;
 
-    wcc(wcc_coverage, ploc)
+#[inline]
+pub(crate) fn wcc_complexity_threshold(complexity: Complexity) -> f64 {
+    complexity_threshold(complexity) * WCC_COMPLEXITY_FACTOR
 }
 
 #[inline]
 pub(crate) fn wcc(wcc_coverage: f64, ploc: f64) -> f64 {
     round_sd((wcc_coverage / ploc) * 100.0)
 }
+
+// Wcc built-in metric.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WccMetric(pub(crate) MetricsThresholds);
+
+impl Metric for WccMetric {
+    fn name(&self) -> &str {
+        "wcc"
+    }
+
+    fn score(&self, input: &MetricInput) -> f64 {
+        wcc(input.wcc_covered_lines, input.ploc)
+    }
+
+    fn exceeds_threshold(&self, score: f64, _complexity: Complexity) -> bool {
+        score < self.0.wcc
+    }
+}
@@ -0,0 +1,96 @@
+use serde::Deserialize;
+use std::{fs::File, io::BufReader, path::Path};
+
+use crate::error::Result;
+
+use super::{Mutant, Mutations};
+
+#[derive(Debug, Deserialize)]
+struct Outcomes {
+    outcomes: Vec<Outcome>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Outcome {
+    scenario: Scenario,
+    summary: String,
+}
+
+#[derive(Debug, Deserialize)]
+enum Scenario {
+    Baseline,
+    Mutant(MutantScenario),
+}
+
+#[derive(Debug, Deserialize)]
+struct MutantScenario {
+    file: String,
+    // Older cargo-mutants versions only report the line of the mutant.
+    span: Option<Span>,
+    line: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Span {
+    start: LineColumn,
+}
+
+#[derive(Debug, Deserialize)]
+struct LineColumn {
+    line: usize,
+}
+
+impl Mutations {
+    // Parses the `outcomes.json` file written by cargo-mutants.
+    //
+    // Caught and timed out mutants are considered killed, missed mutants survived,
+    // while unviable mutants and the baseline are discarded.
+    pub(crate) fn from_cargo_mutants(json_path: &Path, project_path: &Path) -> Result<Mutations> {
+        let outcomes: Outcomes = serde_json::from_reader(BufReader::new(File::open(json_path)?))?;
+        let mut mutations = Mutations::default();
+
+        for outcome in outcomes.outcomes {
+            let killed = match outcome.summary.as_str() {
+                "CaughtMutant" | "Timeout" => true,
+                "MissedMutant" => false,
+                _ => continue,
+            };
+
+            if let Scenario::Mutant(mutant) = outcome.scenario {
+                if let Some(line) = mutant.span.map(|span| span.start.line).or(mutant.line) {
+                    mutations.insert(project_path, &mutant.file, Mutant { line, killed });
+                }
+            }
+        }
+
+        Ok(mutations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Mutations;
+    use std::path::Path;
+
+    const CARGO_MUTANTS_PATH: &str = "./tests/mutations_files/outcomes.json";
+
+    #[test]
+    fn test_cargo_mutants() {
+        let mutations =
+            Mutations::from_cargo_mutants(Path::new(CARGO_MUTANTS_PATH), Path::new("project/"))
+                .unwrap();
+
+        insta::with_settings!({sort_maps => true}, {
+            insta::assert_yaml_snapshot!(mutations, @r###"
+            project/src/app.rs:
+              - line: 82
+                killed: true
+              - line: 209
+                killed: false
+              - line: 213
+                killed: true
+            "###);
+        });
+    }
+}
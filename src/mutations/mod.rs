@@ -0,0 +1,40 @@
+pub(crate) mod cargo_mutants;
+pub(crate) mod stryker;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::resolve_path;
+
+// Mutant produced by a mutation testing tool.
+//
+// Mutants which could not be tested, such as unviable ones,
+// are discarded while parsing, so a mutant is either killed or survived.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct Mutant {
+    pub(crate) line: usize,
+    pub(crate) killed: bool,
+}
+
+// Mutants of each file, using the path of the files as the key.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct Mutations(pub(crate) HashMap<PathBuf, Vec<Mutant>>);
+
+impl Mutations {
+    #[inline]
+    pub(crate) fn get(&self, file: &Path) -> &[Mutant] {
+        self.0.get(file).map_or(&[], Vec::as_slice)
+    }
+
+    #[inline]
+    fn insert(&mut self, project_path: &Path, file: &str, mutant: Mutant) {
+        self.0
+            .entry(resolve_path(project_path, file))
+            .or_default()
+            .push(mutant);
+    }
+}
@@ -0,0 +1,93 @@
+use serde::Deserialize;
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
+
+use crate::error::Result;
+
+use super::{Mutant, Mutations};
+
+// Mutation testing report following the mutation-testing-elements schema,
+// produced by Stryker and by PIT through its report plugin.
+#[derive(Debug, Deserialize)]
+struct Report {
+    files: HashMap<String, ReportFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportFile {
+    mutants: Vec<ReportMutant>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportMutant {
+    location: Location,
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Location {
+    start: Position,
+}
+
+#[derive(Debug, Deserialize)]
+struct Position {
+    line: usize,
+}
+
+impl Mutations {
+    // Parses a mutation testing report.
+    //
+    // Killed and timed out mutants are considered killed, survived and
+    // not covered mutants survived, while all the other ones are discarded.
+    pub(crate) fn from_stryker(json_path: &Path, project_path: &Path) -> Result<Mutations> {
+        let report: Report = serde_json::from_reader(BufReader::new(File::open(json_path)?))?;
+        let mut mutations = Mutations::default();
+
+        for (file, report_file) in report.files {
+            for mutant in report_file.mutants {
+                let killed = match mutant.status.as_str() {
+                    "Killed" | "Timeout" => true,
+                    "Survived" | "NoCoverage" => false,
+                    _ => continue,
+                };
+
+                mutations.insert(
+                    project_path,
+                    &file,
+                    Mutant {
+                        line: mutant.location.start.line,
+                        killed,
+                    },
+                );
+            }
+        }
+
+        Ok(mutations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Mutations;
+    use std::path::Path;
+
+    const STRYKER_PATH: &str = "./tests/mutations_files/mutation-report.json";
+
+    #[test]
+    fn test_stryker() {
+        let mutations =
+            Mutations::from_stryker(Path::new(STRYKER_PATH), Path::new("project/")).unwrap();
+
+        insta::with_settings!({sort_maps => true}, {
+            insta::assert_yaml_snapshot!(mutations, @r###"
+            project/src/app.ts:
+              - line: 3
+                killed: true
+              - line: 5
+                killed: false
+              - line: 7
+                killed: false
+            "###);
+        });
+    }
+}
@@ -0,0 +1,113 @@
+use std::{fs, path::Path};
+
+use crate::{
+    concurrent::{MetricsData, WccOutput},
+    error::{Error, Result},
+    metrics::MetricsThresholds,
+    Complexity,
+};
+
+use super::{escape_xml, get_metrics_data, Rating, WccPrinter};
+
+pub(crate) const WCC_BADGE: &str = "wcc.svg";
+pub(crate) const CRAP_BADGE: &str = "crap.svg";
+
+const GREEN: &str = "#4c1";
+const YELLOW: &str = "#dfb317";
+const RED: &str = "#e05d44";
+
+// Approximate width of a Verdana 11px character, and horizontal padding of a badge half.
+const CHAR_WIDTH: usize = 7;
+const PADDING: usize = 10;
+
+pub(crate) struct BadgePrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_dir: &'a Path,
+    pub(crate) complexity: Complexity,
+    pub(crate) thresholds: MetricsThresholds,
+}
+
+impl BadgePrinter<'_> {
+    // Total and average project metrics data of the badges complexity metric,
+    // which is always computed since the options are checked before the analysis.
+    fn get_project_data(&self) -> Result<(&MetricsData, &MetricsData)> {
+        let project = &self.wcc_output.project;
+        let find = |metrics| {
+            get_metrics_data(metrics)
+                .find(|(complexity, _)| *complexity == self.complexity)
+                .map(|(_, data)| data)
+                .ok_or(Error::Metrics)
+        };
+
+        Ok((find(&project.total)?, find(&project.average)?))
+    }
+}
+
+#[inline]
+fn color(rating: Rating) -> &'static str {
+    match rating {
+        Rating::Good => GREEN,
+        Rating::Warning => YELLOW,
+        Rating::Bad => RED,
+    }
+}
+
+// Renders a shields-style flat badge.
+fn format_badge(label: &str, value: &str, color: &str) -> String {
+    let label_width = label.chars().count() * CHAR_WIDTH + PADDING;
+    let value_width = value.chars().count() * CHAR_WIDTH + PADDING;
+    let width = label_width + value_width;
+    let (label_x, value_x) = (label_width / 2, label_width + value_width / 2);
+    let (label, value) = (escape_xml(label), escape_xml(value));
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}">
+  <title>{label}: {value}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r">
+    <rect width="{width}" height="20" rx="3" fill="#fff"/>
+  </clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text>
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{value_x}" y="15" fill="#010101" fill-opacity=".3">{value}</text>
+    <text x="{value_x}" y="14">{value}</text>
+  </g>
+</svg>
+"##
+    )
+}
+
+impl WccPrinter for BadgePrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let (total, average) = self.get_project_data()?;
+        fs::write(
+            self.output_dir.join(WCC_BADGE),
+            format_badge(
+                &format!("wcc {}", self.complexity),
+                &format!("{}%", total.wcc),
+                color(Rating::wcc(total.wcc, self.thresholds)),
+            ),
+        )?;
+        fs::write(
+            self.output_dir.join(CRAP_BADGE),
+            format_badge(
+                &format!("crap {}", self.complexity),
+                &average.crap.to_string(),
+                color(Rating::crap(average.crap, self.complexity, self.thresholds)),
+            ),
+        )?;
+
+        Ok(())
+    }
+}
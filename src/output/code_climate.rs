@@ -0,0 +1,187 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::Serialize;
+
+use crate::{concurrent::WccOutput, error::Result, metrics::MetricsThresholds, Mode};
+
+use super::{get_entities, get_violations, Entity, Violation, WccPrinter, WCC_RULE_ID};
+
+const ISSUE_TYPE: &str = "issue";
+const CATEGORY: &str = "Complexity";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Minor,
+    Major,
+    Critical,
+    Blocker,
+}
+
+impl Severity {
+    // Scales the severity by how far the score is beyond the threshold.
+    fn new(violation: &Violation) -> Self {
+        let Some(threshold) = violation.threshold else {
+            return Severity::Minor;
+        };
+
+        // Wcc is a coverage, so it cannot exceed its threshold by far:
+        // it only becomes major when it is below the half of its threshold.
+        if violation.rule_id == WCC_RULE_ID {
+            return if violation.score < threshold / 2.0 {
+                Severity::Major
+            } else {
+                Severity::Minor
+            };
+        }
+
+        match violation.score / threshold {
+            ratio if ratio < 1.5 => Severity::Minor,
+            ratio if ratio < 2.0 => Severity::Major,
+            ratio if ratio < 4.0 => Severity::Critical,
+            _ => Severity::Blocker,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CodeClimateIssue<'a> {
+    #[serde(rename = "type")]
+    issue_type: &'static str,
+    check_name: String,
+    description: String,
+    categories: [&'static str; 1],
+    severity: Severity,
+    fingerprint: String,
+    location: CodeClimateLocation<'a>,
+}
+
+#[derive(Serialize)]
+struct CodeClimateLocation<'a> {
+    path: &'a str,
+    lines: CodeClimateLines,
+}
+
+#[derive(Serialize)]
+struct CodeClimateLines {
+    begin: usize,
+    end: usize,
+}
+
+pub(crate) struct CodeClimatePrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) mode: Mode,
+    pub(crate) thresholds: MetricsThresholds,
+}
+
+impl CodeClimatePrinter<'_> {
+    fn get_issues(&self) -> Vec<CodeClimateIssue<'_>> {
+        let mut entities = get_entities(self.wcc_output, self.mode);
+        // Sort entities by location, since files are processed concurrently.
+        entities.sort_by(|a, b| (a.file, a.lines()).cmp(&(b.file, b.lines())));
+
+        // Occurrences of each function name in a file, so that functions
+        // sharing the same name get distinct fingerprints.
+        let mut occurrences: HashMap<(&str, &str), usize> = HashMap::new();
+        let mut issues = Vec::new();
+        for entity in entities {
+            let identity = get_identity(&entity);
+            let occurrence = occurrences.entry((entity.file, identity)).or_default();
+            for violation in get_violations(entity.metrics, self.thresholds) {
+                let check_name = format!("{}-{}", violation.rule_id, violation.complexity);
+                let fingerprint = md5::compute(format!(
+                    "{}\0{identity}\0{occurrence}\0{check_name}",
+                    entity.file
+                ));
+                let (begin, end) = entity.lines().unwrap_or((1, 1));
+                issues.push(CodeClimateIssue {
+                    issue_type: ISSUE_TYPE,
+                    description: violation.message(entity.name()),
+                    categories: [CATEGORY],
+                    severity: Severity::new(&violation),
+                    fingerprint: format!("{fingerprint:x}"),
+                    location: CodeClimateLocation {
+                        path: entity.file,
+                        lines: CodeClimateLines { begin, end },
+                    },
+                    check_name,
+                });
+            }
+            *occurrence += 1;
+        }
+
+        issues
+    }
+}
+
+// Identity of an entity which does not change when its code moves:
+// function names are stripped of their line range.
+fn get_identity<'a>(entity: &Entity<'a>) -> &'a str {
+    let name = entity.name();
+    match (entity.function, name.rsplit_once('(')) {
+        (Some(_), Some((identity, _))) if name.ends_with(')') => identity,
+        _ => name,
+    }
+}
+
+impl WccPrinter for CodeClimatePrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let issues = self.get_issues();
+        let issues_json = serde_json::to_string_pretty(&issues)?;
+        fs::write(self.output_path, issues_json)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::CRAP_RULE_ID;
+    use super::*;
+    use crate::Complexity;
+
+    fn violation(rule_id: &str, score: f64, threshold: Option<f64>) -> Violation<'static> {
+        Violation {
+            rule_id: rule_id.to_owned(),
+            metric: "",
+            complexity: Complexity::Cyclomatic,
+            score,
+            threshold,
+        }
+    }
+
+    #[test]
+    fn severity_scales_with_threshold_distance() {
+        assert_eq!(
+            Severity::new(&violation(WCC_RULE_ID, 40.0, Some(60.0))),
+            Severity::Minor
+        );
+        assert_eq!(
+            Severity::new(&violation(WCC_RULE_ID, 0.0, Some(60.0))),
+            Severity::Major
+        );
+        assert_eq!(
+            Severity::new(&violation(CRAP_RULE_ID, 20.0, Some(16.4))),
+            Severity::Minor
+        );
+        assert_eq!(
+            Severity::new(&violation(CRAP_RULE_ID, 30.0, Some(16.4))),
+            Severity::Major
+        );
+        assert_eq!(
+            Severity::new(&violation(CRAP_RULE_ID, 50.0, Some(16.4))),
+            Severity::Critical
+        );
+        assert_eq!(
+            Severity::new(&violation(CRAP_RULE_ID, 80.0, Some(16.4))),
+            Severity::Blocker
+        );
+        assert_eq!(
+            Severity::new(&violation("custom", 80.0, None)),
+            Severity::Minor
+        );
+    }
+}
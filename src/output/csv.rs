@@ -0,0 +1,256 @@
+use std::path::Path;
+
+use crate::{
+    concurrent::{
+        files::FileMetrics,
+        functions::FunctionMetrics,
+        targets::{Targets, TargetsData},
+        LineRange, Metrics, MetricsData, WccOutput,
+    },
+    error::Result,
+    Complexity, Mode,
+};
+
+use super::{get_metrics_data, WccPrinter};
+
+// Columns of the CSV and TSV outputs which do not depend on the analysis options.
+// Their order is part of the output contract, so new columns must only be appended.
+const COLUMNS: [&str; 8] = [
+    "kind",
+    "file",
+    "function",
+    "start_line",
+    "end_line",
+    "coverage",
+    "hit_coverage",
+    "mutation_score",
+];
+
+// Columns of the metrics computed with each complexity metric, prefixed by its name.
+const METRICS_DATA_COLUMNS: [&str; 10] = [
+    "wcc",
+    "crap",
+    "skunk",
+    "complexity",
+    "is_complex",
+    "hit_wcc",
+    "hit_crap",
+    "hit_skunk",
+    "mutation_wcc",
+    "mutation_crap",
+];
+
+// Columns following the cyclomatic and cognitive metrics.
+const LINES_COLUMNS: [&str; 2] = ["instrumented_lines", "uncovered_lines"];
+
+// Columns of the coverage targets, prefixed by the complexity metric name.
+const TARGETS_COLUMNS: [&str; 3] = [
+    "crap_coverage_target",
+    "skunk_coverage_target",
+    "wcc_lines_target",
+];
+
+pub(crate) const CSV_DELIMITER: u8 = b',';
+pub(crate) const TSV_DELIMITER: u8 = b'\t';
+
+pub(crate) struct CsvPrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) mode: Mode,
+    pub(crate) delimiter: u8,
+}
+
+impl CsvPrinter<'_> {
+    // Columns which are always present, followed by the metrics computed with
+    // the additional complexity metrics and by the custom metrics, if any.
+    fn get_columns(&self) -> Vec<String> {
+        let mut columns: Vec<_> = COLUMNS.iter().map(|column| (*column).to_owned()).collect();
+        let mut metrics_data: Vec<_> = get_metrics_data(&self.wcc_output.project.total).collect();
+        let additional = metrics_data.split_off(2);
+        for (complexity, _) in &metrics_data {
+            columns.extend(prefixed_columns(*complexity, &METRICS_DATA_COLUMNS));
+        }
+        columns.extend(LINES_COLUMNS.iter().map(|column| (*column).to_owned()));
+        for (complexity, _) in &metrics_data {
+            columns.extend(prefixed_columns(*complexity, &TARGETS_COLUMNS));
+        }
+        for (complexity, _) in &additional {
+            columns.extend(prefixed_columns(*complexity, &METRICS_DATA_COLUMNS));
+        }
+        for (complexity, data) in metrics_data.iter().chain(&additional) {
+            for value in &data.custom {
+                let name = value.name.as_str();
+                columns.extend(prefixed_columns(
+                    *complexity,
+                    &[name, &format!("{name}_exceeds_threshold")],
+                ));
+            }
+        }
+
+        columns
+    }
+
+    // One row per file and, in functions mode, one row per function
+    // right after the row of its file.
+    fn get_records(&self) -> Vec<Vec<String>> {
+        let mut files: Vec<_> = self.wcc_output.files.iter().collect();
+        // Sort files by path, since they are processed concurrently.
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut records = Vec::new();
+        for file in files {
+            records.push(format_file_record(file));
+            if let (Mode::Functions, Some(functions)) = (self.mode, &file.functions) {
+                let mut functions: Vec<_> = functions.iter().collect();
+                functions.sort_by_key(|function| (function.start_line, function.end_line));
+                records.extend(
+                    functions
+                        .into_iter()
+                        .map(|function| format_function_record(&file.name, function)),
+                );
+            }
+        }
+
+        records
+    }
+}
+
+// Columns prefixed by the complexity metric name, in snake case.
+fn prefixed_columns<'a>(
+    complexity: Complexity,
+    columns: &'a [impl AsRef<str>],
+) -> impl Iterator<Item = String> + 'a {
+    let prefix = complexity.to_string().replace('-', "_");
+    columns
+        .iter()
+        .map(move |column| format!("{prefix}_{}", column.as_ref()))
+}
+
+fn format_file_record(file: &FileMetrics) -> Vec<String> {
+    let mut record = vec![
+        "file".to_owned(),
+        file.name.clone(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ];
+    format_metrics(
+        &mut record,
+        &file.metrics,
+        file.instrumented_lines,
+        &[],
+        file.targets.as_ref(),
+    );
+
+    record
+}
+
+fn format_function_record(file: &str, function: &FunctionMetrics) -> Vec<String> {
+    let mut record = vec![
+        "function".to_owned(),
+        file.to_owned(),
+        function.name.clone(),
+        function.start_line.to_string(),
+        function.end_line.to_string(),
+    ];
+    format_metrics(
+        &mut record,
+        &function.metrics,
+        function.instrumented_lines,
+        &function.uncovered_lines,
+        function.targets.as_ref(),
+    );
+
+    record
+}
+
+// Formats the metrics in the same order of the columns.
+fn format_metrics(
+    record: &mut Vec<String>,
+    metrics: &Metrics,
+    instrumented_lines: usize,
+    uncovered_lines: &[LineRange],
+    targets: Option<&Targets>,
+) {
+    record.push(metrics.coverage.to_string());
+    record.push(format_optional(metrics.hit_coverage));
+    record.push(format_optional(metrics.mutation_score));
+    format_metrics_data(record, &metrics.cyclomatic);
+    format_metrics_data(record, &metrics.cognitive);
+    record.push(instrumented_lines.to_string());
+    record.push(format_line_ranges(uncovered_lines));
+    format_targets(record, targets.map(|targets| &targets.cyclomatic));
+    format_targets(record, targets.map(|targets| &targets.cognitive));
+    for data in metrics.complexities.values() {
+        format_metrics_data(record, data);
+    }
+    for (_, data) in get_metrics_data(metrics) {
+        for value in &data.custom {
+            record.extend([value.score.to_string(), value.exceeds_threshold.to_string()]);
+        }
+    }
+}
+
+fn format_metrics_data(record: &mut Vec<String>, metrics_data: &MetricsData) {
+    record.extend([
+        metrics_data.wcc.to_string(),
+        metrics_data.crap.to_string(),
+        metrics_data.skunk.to_string(),
+        metrics_data.complexity.to_string(),
+        metrics_data.is_complex.to_string(),
+        format_optional(metrics_data.hit_wcc),
+        format_optional(metrics_data.hit_crap),
+        format_optional(metrics_data.hit_skunk),
+        format_optional(metrics_data.mutation_wcc),
+        format_optional(metrics_data.mutation_crap),
+    ]);
+}
+
+fn format_targets(record: &mut Vec<String>, targets: Option<&TargetsData>) {
+    record.extend([
+        format_optional(targets.and_then(|targets| targets.crap_coverage)),
+        format_optional(targets.and_then(|targets| targets.skunk_coverage)),
+        targets
+            .and_then(|targets| targets.wcc_lines)
+            .map(|lines| lines.to_string())
+            .unwrap_or_default(),
+    ]);
+}
+
+// Line ranges separated by spaces, e.g. `3-5 9`.
+fn format_line_ranges(line_ranges: &[LineRange]) -> String {
+    line_ranges
+        .iter()
+        .map(|range| {
+            if range.start == range.end {
+                range.start.to_string()
+            } else {
+                format!("{}-{}", range.start, range.end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Absent values are left empty.
+#[inline]
+fn format_optional(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+impl WccPrinter for CsvPrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let mut writer = ::csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_path(self.output_path)?;
+        writer.write_record(self.get_columns())?;
+        for record in self.get_records() {
+            writer.write_record(record)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::{fmt::Write, fs, path::Path};
+
+use crate::{concurrent::WccOutput, error::Result, metrics::MetricsThresholds, Mode};
+
+use super::{escape_xml, get_entities, get_metrics_data, get_violations, Entity, WccPrinter};
+
+const TEST_SUITES_NAME: &str = "weighted-code-coverage";
+const FAILURE_TYPE: &str = "complex";
+
+pub(crate) struct JunitPrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) mode: Mode,
+    pub(crate) thresholds: MetricsThresholds,
+}
+
+impl JunitPrinter<'_> {
+    fn format_test_case(&self, entity: &Entity) -> String {
+        // Function names already end with their lines, which are in the line attribute too.
+        let mut test_case = format!(
+            "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\"",
+            escape_xml(entity.name()),
+            escape_xml(entity.file),
+            escape_xml(entity.file),
+        );
+        if let Some((start_line, _)) = entity.lines() {
+            let _ = write!(test_case, " line=\"{start_line}\"");
+        }
+
+        let complexities: Vec<_> = get_metrics_data(entity.metrics)
+            .filter(|(_, data)| data.is_complex)
+            .map(|(complexity, _)| complexity.to_string())
+            .collect();
+        if complexities.is_empty() {
+            test_case.push_str("/>\n");
+            return test_case;
+        }
+
+        let violations = get_violations(entity.metrics, self.thresholds);
+        let details: Vec<_> = violations
+            .iter()
+            .map(|violation| violation.message(entity.name()))
+            .collect();
+        let _ = write!(
+            test_case,
+            ">\n      <failure message=\"{} is complex with {} complexity\" type=\"{FAILURE_TYPE}\">{}</failure>\n    </testcase>\n",
+            escape_xml(entity.name()),
+            complexities.join(", "),
+            escape_xml(&details.join("\n")),
+        );
+
+        test_case
+    }
+
+    fn format_report(&self) -> String {
+        let mut entities = get_entities(self.wcc_output, self.mode);
+        // Sort test cases by location, since files are processed concurrently.
+        entities.sort_by(|a, b| (a.file, a.lines()).cmp(&(b.file, b.lines())));
+
+        let failures = entities.iter().filter(|entity| is_failure(entity)).count();
+        let suite_name = match self.mode {
+            Mode::Files => "files",
+            Mode::Functions => "functions",
+        };
+
+        let mut report = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            report,
+            "<testsuites name=\"{TEST_SUITES_NAME}\" tests=\"{}\" failures=\"{failures}\">",
+            entities.len()
+        );
+        let _ = writeln!(
+            report,
+            "  <testsuite name=\"{suite_name}\" tests=\"{}\" failures=\"{failures}\">",
+            entities.len()
+        );
+        for entity in &entities {
+            report.push_str(&self.format_test_case(entity));
+        }
+        report.push_str("  </testsuite>\n</testsuites>\n");
+
+        report
+    }
+}
+
+// An entity fails when it is complex according to any complexity metric.
+#[inline]
+fn is_failure(entity: &Entity) -> bool {
+    get_metrics_data(entity.metrics).any(|(_, data)| data.is_complex)
+}
+
+impl WccPrinter for JunitPrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        fs::write(self.output_path, self.format_report())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_xml;
+
+    #[test]
+    fn escapes_xml() {
+        assert_eq!(
+            escape_xml("<T as Trait<'a>>::f & \"g\""),
+            "&lt;T as Trait&lt;&apos;a&gt;&gt;::f &amp; &quot;g&quot;"
+        );
+    }
+}
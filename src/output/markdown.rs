@@ -0,0 +1,205 @@
+use std::{fmt::Write, fs, path::Path};
+
+use crate::{
+    concurrent::{Metrics, MetricsData, ProjectMetrics, WccOutput},
+    error::Result,
+    metrics::round_sd,
+    Mode, Sort,
+};
+
+use super::{get_entities, sort_by_risk, Entity, WccPrinter};
+
+pub(crate) const DEFAULT_MARKDOWN_TOP: usize = 10;
+
+pub(crate) struct MarkdownPrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) mode: Mode,
+    pub(crate) sort_by: &'a Sort,
+    pub(crate) top: usize,
+    pub(crate) max_size: Option<usize>,
+}
+
+impl MarkdownPrinter<'_> {
+    fn format_summary(&self) -> String {
+        let project = &self.wcc_output.project;
+        let mut summary = String::from("## Weighted Code Coverage\n\n");
+        summary.push_str("| Metric | Total | Min | Max | Average |\n");
+        summary.push_str("| --- | ---: | ---: | ---: | ---: |\n");
+        summary.push_str(&format_project_row(project, "Coverage", |m| m.coverage));
+        for (complexity, metrics_data) in [
+            (
+                "cyclomatic",
+                (|m| &m.cyclomatic) as fn(&Metrics) -> &MetricsData,
+            ),
+            ("cognitive", |m| &m.cognitive),
+        ] {
+            summary.push_str(&format_project_row(
+                project,
+                &format!("Wcc ({complexity})"),
+                |m| metrics_data(m).wcc,
+            ));
+            summary.push_str(&format_project_row(
+                project,
+                &format!("CRAP ({complexity})"),
+                |m| metrics_data(m).crap,
+            ));
+            summary.push_str(&format_project_row(
+                project,
+                &format!("Skunk ({complexity})"),
+                |m| metrics_data(m).skunk,
+            ));
+            summary.push_str(&format_project_row(
+                project,
+                &format!("Complexity ({complexity})"),
+                |m| metrics_data(m).complexity,
+            ));
+        }
+
+        let entities = get_entities(self.wcc_output, self.mode);
+        let complex_cyclomatic = entities
+            .iter()
+            .filter(|entity| entity.metrics.cyclomatic.is_complex)
+            .count();
+        let complex_cognitive = entities
+            .iter()
+            .filter(|entity| entity.metrics.cognitive.is_complex)
+            .count();
+        let _ = write!(
+            summary,
+            "\n**Complex {}:** {complex_cyclomatic} of {} (cyclomatic), {complex_cognitive} of {} (cognitive)\n",
+            self.entities_name(),
+            entities.len(),
+            entities.len(),
+        );
+
+        summary
+    }
+
+    // Entities sorted by risk according to the chosen sort metric.
+    fn get_riskiest_entities(&self) -> Vec<Entity<'_>> {
+        let mut entities = get_entities(self.wcc_output, self.mode);
+        sort_by_risk(&mut entities, self.sort_by);
+        entities.truncate(self.top);
+
+        entities
+    }
+
+    // Header of the riskiest table, listing the number of rows actually shown.
+    fn format_riskiest_header(&self, shown: usize) -> String {
+        let mut header = format!(
+            "\n### Top {shown} riskiest {} by {}\n\n",
+            self.entities_name(),
+            self.sort_by
+        );
+        match self.mode {
+            Mode::Files => {
+                header.push_str("| File | Coverage | Wcc | CRAP | Skunk | Complexity |\n");
+                header.push_str("| --- | ---: | ---: | ---: | ---: | ---: |\n");
+            }
+            Mode::Functions => {
+                header
+                    .push_str("| File | Function | Coverage | Wcc | CRAP | Skunk | Complexity |\n");
+                header.push_str("| --- | --- | ---: | ---: | ---: | ---: | ---: |\n");
+            }
+        }
+
+        header
+    }
+
+    // Riskiest table with the first `shown` rows, followed by a note on the omitted ones.
+    fn format_riskiest_table(&self, rows: &[String], shown: usize) -> String {
+        let mut table = self.format_riskiest_header(shown);
+        rows[..shown].iter().for_each(|row| table.push_str(row));
+        let omitted = rows.len() - shown;
+        if omitted > 0 {
+            let _ = write!(
+                table,
+                "\n_{omitted} more {} omitted._\n",
+                self.entities_name()
+            );
+        }
+
+        table
+    }
+
+    fn format_riskiest_row(&self, entity: &Entity) -> String {
+        let metrics = entity.metrics;
+        let complex_mark = if metrics.cyclomatic.is_complex {
+            " :warning:"
+        } else {
+            ""
+        };
+        let name = match self.mode {
+            Mode::Files => format!("`{}`{complex_mark}", entity.file),
+            Mode::Functions => format!("`{}` | `{}`{complex_mark}", entity.file, entity.name()),
+        };
+
+        format!(
+            "| {name} | {}% | {} | {} | {} | {} |\n",
+            metrics.coverage,
+            metrics.cyclomatic.wcc,
+            metrics.cyclomatic.crap,
+            metrics.cyclomatic.skunk,
+            metrics.cyclomatic.complexity,
+        )
+    }
+
+    fn entities_name(&self) -> &'static str {
+        match self.mode {
+            Mode::Files => "files",
+            Mode::Functions => "functions",
+        }
+    }
+
+    // Formats the report within the maximum size, dropping the rows of the riskiest
+    // table which do not fit, the whole table when none of them fits, and the summary
+    // too when it does not fit either.
+    fn format_output(&self) -> String {
+        let max_size = self.max_size.unwrap_or(usize::MAX);
+        let mut output = self.format_summary();
+        if output.len() > max_size {
+            return String::new();
+        }
+
+        let rows: Vec<_> = self
+            .get_riskiest_entities()
+            .iter()
+            .map(|entity| self.format_riskiest_row(entity))
+            .collect();
+        if let Some(table) = (1..=rows.len())
+            .rev()
+            .map(|shown| self.format_riskiest_table(&rows, shown))
+            .find(|table| output.len() + table.len() <= max_size)
+        {
+            output.push_str(&table);
+        }
+
+        output
+    }
+}
+
+impl WccPrinter for MarkdownPrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        fs::write(self.output_path, self.format_output())?;
+
+        Ok(())
+    }
+}
+
+#[inline]
+fn format_project_row(
+    project: &ProjectMetrics,
+    name: &str,
+    value: impl Fn(&Metrics) -> f64,
+) -> String {
+    format!(
+        "| {name} | {} | {} | {} | {} |\n",
+        round_sd(value(&project.total)),
+        round_sd(value(&project.min)),
+        round_sd(value(&project.max)),
+        round_sd(value(&project.average)),
+    )
+}
@@ -0,0 +1,989 @@
+mod badge;
+mod code_climate;
+mod csv;
+mod junit;
+mod markdown;
+mod priorities;
+mod sarif;
+mod scatter;
+mod sonar;
+mod table;
+mod treemap;
+mod trend;
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use minijinja::{context, Environment, ErrorKind, Value};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::concurrent::{
+    files::FileMetrics, functions::FunctionMetrics, targets::Targets, Grcov, LineRange, Metrics,
+    MetricsData, ProjectMetrics, WccOutput,
+};
+use crate::metrics::{
+    crap::CrapMetric, skunk::SkunkMetric, wcc::WccMetric, Metric, MetricsThresholds,
+};
+use crate::{error::*, history::History, smells::Smell, Complexity, Mode, Sort};
+
+pub(crate) use self::badge::BadgePrinter;
+pub(crate) use self::code_climate::CodeClimatePrinter;
+pub(crate) use self::csv::{CsvPrinter, CSV_DELIMITER, TSV_DELIMITER};
+pub(crate) use self::junit::JunitPrinter;
+pub(crate) use self::markdown::{MarkdownPrinter, DEFAULT_MARKDOWN_TOP};
+pub(crate) use self::priorities::{PrioritiesPrinter, DEFAULT_PRIORITIES_TOP};
+pub(crate) use self::sarif::SarifPrinter;
+pub(crate) use self::sonar::{SonarCoveragePrinter, SonarIssuesPrinter};
+pub(crate) use self::table::TablePrinter;
+
+use self::scatter::get_scatter;
+use self::treemap::get_treemap;
+use self::trend::get_trend;
+
+static BASE: (&str, &str) = ("base.html", include_str!("../../templates/base.html.jinja"));
+
+static FILES: (&str, &str) = (
+    "files.html",
+    include_str!("../../templates/files.html.jinja"),
+);
+
+static SOURCE: (&str, &str) = (
+    "source.html",
+    include_str!("../../templates/source.html.jinja"),
+);
+
+static FILE_DETAILS: (&str, &str) = (
+    "file_details.html",
+    include_str!("../../templates/file_details.html.jinja"),
+);
+
+static NAVBAR: (&str, &str) = (
+    "navbar.html",
+    include_str!("../../templates/navbar.html.jinja"),
+);
+
+static ASSETS_LINKS: (&str, &str) = (
+    "assets.html",
+    include_str!("../../templates/assets.html.jinja"),
+);
+
+static STYLE: (&str, &str) = ("style.css", include_str!("../../templates/css/style.css"));
+
+static COMMON_TOOLTIPS: (&str, &str) = (
+    "common_tooltips.js",
+    include_str!("../../templates/js/common_tooltips.js"),
+);
+
+static BASE_TOOLTIPS: (&str, &str) = (
+    "base_tooltips.js",
+    include_str!("../../templates/js/base_tooltips.js"),
+);
+
+static FILE_DETAILS_TOOLTIPS: (&str, &str) = (
+    "file_details_tooltips.js",
+    include_str!("../../templates/js/file_details_tooltips.js"),
+);
+
+static TABLE: (&str, &str) = ("table.js", include_str!("../../templates/js/table.js"));
+
+static TABLE_CONTROLS: (&str, &str) = (
+    "table_controls.html",
+    include_str!("../../templates/table_controls.html.jinja"),
+);
+
+static COMPLEXITY: (&str, &str) = (
+    "complexity.js",
+    include_str!("../../templates/js/complexity.js"),
+);
+
+static SINGLE: (&str, &str) = (
+    "single.html",
+    include_str!("../../templates/single.html.jinja"),
+);
+
+// Built-in html templates, which can be overridden by name.
+static TEMPLATES: [&(&str, &str); 14] = [
+    &BASE,
+    &FILES,
+    &SOURCE,
+    &FILE_DETAILS,
+    &NAVBAR,
+    &ASSETS_LINKS,
+    &STYLE,
+    &COMMON_TOOLTIPS,
+    &BASE_TOOLTIPS,
+    &FILE_DETAILS_TOOLTIPS,
+    &TABLE,
+    &TABLE_CONTROLS,
+    &COMPLEXITY,
+    &SINGLE,
+];
+
+const OUTPUT_HTML_INDEX: &str = "index.html";
+
+// Third-party assets of the html output: name used by the templates,
+// CDN url, and file name of the vendored copy.
+const ASSETS: [(&str, &str, &str); 5] = [
+    (
+        "bulma",
+        "https://cdn.jsdelivr.net/npm/bulma@1.0.0/css/bulma.min.css",
+        "bulma.min.css",
+    ),
+    (
+        "fontawesome",
+        "https://cdn.jsdelivr.net/npm/@fortawesome/fontawesome-free@6.5.1/js/all.min.js",
+        "fontawesome.min.js",
+    ),
+    (
+        "chartjs",
+        "https://cdn.jsdelivr.net/npm/chart.js@4.4.1/dist/chart.umd.js",
+        "chart.umd.js",
+    ),
+    (
+        "popper",
+        "https://cdn.jsdelivr.net/npm/@popperjs/core@2.11.8/dist/umd/popper.min.js",
+        "popper.min.js",
+    ),
+    (
+        "tippy",
+        "https://cdn.jsdelivr.net/npm/tippy.js@6.3.7/dist/tippy-bundle.umd.min.js",
+        "tippy-bundle.umd.min.js",
+    ),
+];
+
+// Vendored copies of the assets, in the same order of `ASSETS`.
+// They are downloaded by `templates/vendor/fetch.sh`.
+#[cfg(feature = "offline-html")]
+static VENDORED_ASSETS: [&[u8]; 5] = [
+    include_bytes!("../../templates/vendor/bulma.min.css"),
+    include_bytes!("../../templates/vendor/fontawesome.min.js"),
+    include_bytes!("../../templates/vendor/chart.umd.js"),
+    include_bytes!("../../templates/vendor/popper.min.js"),
+    include_bytes!("../../templates/vendor/tippy-bundle.umd.min.js"),
+];
+
+// Directory of the vendored assets, relative to the html output.
+#[cfg(feature = "offline-html")]
+const ASSETS_DIR: &str = "assets";
+
+// Prefix of the asset urls replaced by the single file output
+// with the urls of the inlined assets.
+const INLINE_ASSET_PREFIX: &str = "wcc-asset:";
+
+pub(crate) trait WccPrinter {
+    type Output;
+
+    fn print(self) -> Self::Output;
+}
+
+// Code entity reported by the printers: a file in files mode,
+// a function in functions mode.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Entity<'a> {
+    pub(crate) file: &'a str,
+    pub(crate) function: Option<&'a FunctionMetrics>,
+    pub(crate) metrics: &'a Metrics,
+}
+
+impl<'a> Entity<'a> {
+    // Name of the function, or of the file for files.
+    #[inline]
+    pub(crate) fn name(&self) -> &'a str {
+        self.function.map_or(self.file, |function| &function.name)
+    }
+
+    // Line range of the function, if the entity is a function.
+    #[inline]
+    pub(crate) fn lines(&self) -> Option<(usize, usize)> {
+        self.function
+            .map(|function| (function.start_line, function.end_line))
+    }
+}
+
+// Retrieves the entities of the output, in the same order as the output.
+pub(crate) fn get_entities(wcc_output: &WccOutput, mode: Mode) -> Vec<Entity<'_>> {
+    wcc_output
+        .files
+        .iter()
+        .flat_map(|file| -> Vec<Entity> {
+            match (mode, &file.functions) {
+                (Mode::Functions, Some(functions)) => functions
+                    .iter()
+                    .map(|function| Entity {
+                        file: &file.name,
+                        function: Some(function),
+                        metrics: &function.metrics,
+                    })
+                    .collect(),
+                (Mode::Functions, None) => Vec::new(),
+                (Mode::Files, _) => vec![Entity {
+                    file: &file.name,
+                    function: None,
+                    metrics: &file.metrics,
+                }],
+            }
+        })
+        .collect()
+}
+
+// Sorts the entities from the riskiest one according to the sort metric.
+pub(crate) fn sort_by_risk(entities: &mut [Entity], sort_by: &Sort) {
+    // Wcc is a coverage, so its lowest values are the riskiest ones.
+    let ascending = matches!(sort_by, Sort::Wcc | Sort::HitWcc);
+    entities.sort_by(|a, b| {
+        let (a_score, b_score) = (a.metrics.sort_score(sort_by), b.metrics.sort_score(sort_by));
+        let ordering = if ascending {
+            a_score.total_cmp(&b_score)
+        } else {
+            b_score.total_cmp(&a_score)
+        };
+
+        // Ties are broken by CRAP, then by position.
+        ordering
+            .then_with(|| {
+                b.metrics
+                    .cyclomatic
+                    .crap
+                    .total_cmp(&a.metrics.cyclomatic.crap)
+            })
+            .then_with(|| a.file.cmp(b.file))
+            .then_with(|| a.lines().cmp(&b.lines()))
+    });
+}
+
+// Source path and lines coverage of the analyzed files, using their names as the key.
+pub(crate) fn get_sources<'a>(
+    wcc_output: &WccOutput,
+    project_path: &Path,
+    files: &'a [PathBuf],
+    grcov: &'a Grcov,
+) -> HashMap<&'a str, (&'a Path, &'a [Option<i32>])> {
+    let analyzed: HashSet<_> = wcc_output
+        .files
+        .iter()
+        .map(|file| file.name.as_str())
+        .collect();
+
+    files
+        .iter()
+        .filter_map(|file| {
+            let name = grcov.get_file_name(file, project_path)?;
+            let lines_coverage = grcov.get_lines_coverage(file)?;
+            analyzed
+                .contains(name)
+                .then_some((name, (file.as_path(), lines_coverage.as_slice())))
+        })
+        .collect()
+}
+
+// Metrics data of each complexity metric, starting from cyclomatic and cognitive.
+#[inline]
+pub(crate) fn get_metrics_data(
+    metrics: &Metrics,
+) -> impl Iterator<Item = (Complexity, &MetricsData)> {
+    [
+        (Complexity::Cyclomatic, &metrics.cyclomatic),
+        (Complexity::Cognitive, &metrics.cognitive),
+    ]
+    .into_iter()
+    .chain(
+        metrics
+            .complexities
+            .iter()
+            .map(|(complexity, data)| (*complexity, data)),
+    )
+}
+
+// Metric whose score crosses its threshold.
+#[derive(Debug, Clone)]
+pub(crate) struct Violation<'a> {
+    pub(crate) rule_id: String,
+    pub(crate) metric: &'a str,
+    pub(crate) complexity: Complexity,
+    pub(crate) score: f64,
+    // Custom metrics do not expose their thresholds.
+    pub(crate) threshold: Option<f64>,
+}
+
+impl Violation<'_> {
+    // Human-readable description of the violation.
+    pub(crate) fn message(&self, name: &str) -> String {
+        let comparison = match self.threshold {
+            Some(threshold) if self.rule_id == WCC_RULE_ID => {
+                format!("is below the threshold of {threshold}")
+            }
+            Some(threshold) => format!("exceeds the threshold of {threshold}"),
+            None => "crosses its threshold".to_owned(),
+        };
+
+        format!(
+            "{} of {name} is {} and {comparison} with {} complexity",
+            self.metric, self.score, self.complexity
+        )
+    }
+}
+
+pub(crate) const WCC_RULE_ID: &str = "wcc-low";
+pub(crate) const CRAP_RULE_ID: &str = "crap-high";
+pub(crate) const SKUNK_RULE_ID: &str = "skunk-high";
+
+// Retrieves the metrics crossing their thresholds for each complexity metric.
+pub(crate) fn get_violations(
+    metrics: &Metrics,
+    thresholds: MetricsThresholds,
+) -> Vec<Violation<'_>> {
+    let wcc = WccMetric(thresholds);
+    let crap = CrapMetric(thresholds);
+    let skunk = SkunkMetric(thresholds);
+
+    let mut violations = Vec::new();
+    for (complexity, data) in get_metrics_data(metrics) {
+        let built_ins = [
+            (
+                WCC_RULE_ID,
+                "Wcc",
+                data.wcc,
+                &wcc as &dyn Metric,
+                thresholds.wcc(),
+            ),
+            (
+                CRAP_RULE_ID,
+                "CRAP",
+                data.crap,
+                &crap,
+                thresholds.crap(complexity),
+            ),
+            (
+                SKUNK_RULE_ID,
+                "Skunk",
+                data.skunk,
+                &skunk,
+                thresholds.skunk(complexity),
+            ),
+        ];
+        for (rule_id, metric_name, score, metric, threshold) in built_ins {
+            if metric.exceeds_threshold(score, complexity) {
+                violations.push(Violation {
+                    rule_id: rule_id.to_owned(),
+                    metric: metric_name,
+                    complexity,
+                    score,
+                    threshold: Some(threshold),
+                });
+            }
+        }
+
+        violations.extend(
+            data.custom
+                .iter()
+                .filter(|value| value.exceeds_threshold)
+                .map(|value| Violation {
+                    rule_id: value.name.clone(),
+                    metric: &value.name,
+                    complexity,
+                    score: value.score,
+                    threshold: None,
+                }),
+        );
+    }
+
+    violations
+}
+
+// Rating of a score against its threshold: good when the threshold is met,
+// warning within twice the distance from it, bad otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Rating {
+    Good,
+    Warning,
+    Bad,
+}
+
+impl Rating {
+    pub(crate) fn wcc(wcc: f64, thresholds: MetricsThresholds) -> Self {
+        let threshold = thresholds.wcc();
+        if wcc >= threshold {
+            Rating::Good
+        } else if wcc >= threshold / 2.0 {
+            Rating::Warning
+        } else {
+            Rating::Bad
+        }
+    }
+
+    pub(crate) fn crap(crap: f64, complexity: Complexity, thresholds: MetricsThresholds) -> Self {
+        let threshold = thresholds.crap(complexity);
+        if crap <= threshold {
+            Rating::Good
+        } else if crap <= threshold * 2.0 {
+            Rating::Warning
+        } else {
+            Rating::Bad
+        }
+    }
+}
+
+// Escapes the characters which are not allowed in XML attributes and text.
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Version of the json output schema, as `major.minor`.
+///
+/// The major version changes when fields are removed or changed,
+/// the minor version when fields are added.
+pub const SCHEMA_VERSION: &str = "2.0";
+
+/// Thresholds of the metrics, as written in the json output.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportThresholds {
+    /// Wcc threshold.
+    pub wcc: f64,
+    /// CRAP threshold computed with cyclomatic complexity.
+    pub crap_cyclomatic: f64,
+    /// CRAP threshold computed with cognitive complexity.
+    pub crap_cognitive: f64,
+    /// Skunk threshold computed with cyclomatic complexity.
+    pub skunk_cyclomatic: f64,
+    /// Skunk threshold computed with cognitive complexity.
+    pub skunk_cognitive: f64,
+}
+
+impl From<MetricsThresholds> for ReportThresholds {
+    fn from(thresholds: MetricsThresholds) -> Self {
+        Self {
+            wcc: thresholds.wcc(),
+            crap_cyclomatic: thresholds.crap(Complexity::Cyclomatic),
+            crap_cognitive: thresholds.crap(Complexity::Cognitive),
+            skunk_cyclomatic: thresholds.skunk(Complexity::Cyclomatic),
+            skunk_cognitive: thresholds.skunk(Complexity::Cognitive),
+        }
+    }
+}
+
+/// Json output of the weighted code coverage.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WccReport {
+    /// Version of the json output schema.
+    pub schema_version: String,
+    /// Path of the project.
+    pub project: PathBuf,
+    /// Mode used for the analysis.
+    pub mode: Mode,
+    /// Thresholds of the metrics.
+    pub thresholds: ReportThresholds,
+    /// Files.
+    pub files: Vec<FileMetrics>,
+    /// Project metrics.
+    pub project_metrics: ProjectMetrics,
+    /// Files which are complex according to cyclomatic complexity.
+    pub complex_files_cyclomatic: Vec<String>,
+    /// Files which are complex according to cognitive complexity.
+    pub complex_files_cognitive: Vec<String>,
+    /// Ignored files.
+    pub ignored_files: Vec<String>,
+}
+
+impl WccReport {
+    /// Loads a json output, checking that its schema version
+    /// has the same major version of [`SCHEMA_VERSION`].
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let json: serde_json::Value = serde_json::from_slice(&fs::read(path)?)?;
+
+        let schema_version = json
+            .get("schemaVersion")
+            .and_then(|version| version.as_str())
+            .unwrap_or_default();
+        if major_version(schema_version) != major_version(SCHEMA_VERSION) {
+            return Err(Error::SchemaVersion(schema_version.to_owned()));
+        }
+
+        Ok(serde_json::from_value(json)?)
+    }
+
+    /// JSON Schema of the json output.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(WccReport)).unwrap_or_default()
+    }
+}
+
+#[inline]
+pub(crate) fn major_version(version: &str) -> Option<&str> {
+    version
+        .split_once('.')
+        .map(|(major, _)| major)
+        .filter(|major| !major.is_empty())
+}
+
+pub(crate) struct JsonPrinter<'a> {
+    pub(crate) project_path: &'a Path,
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) mode: Mode,
+    pub(crate) thresholds: MetricsThresholds,
+}
+
+impl JsonPrinter<'_> {
+    fn get_complex_files(&self, complexity: Complexity) -> Vec<String> {
+        self.wcc_output
+            .files
+            .iter()
+            .filter(|f| match complexity {
+                Complexity::Cyclomatic => f.metrics.cyclomatic.is_complex,
+                Complexity::Cognitive => f.metrics.cognitive.is_complex,
+                _ => f
+                    .metrics
+                    .complexities
+                    .get(&complexity)
+                    .is_some_and(|metrics| metrics.is_complex),
+            })
+            .map(|f| f.name.clone())
+            .collect()
+    }
+
+    fn format_output(&self) -> WccReport {
+        WccReport {
+            schema_version: SCHEMA_VERSION.to_owned(),
+            project: self.project_path.to_path_buf(),
+            mode: self.mode,
+            thresholds: self.thresholds.into(),
+            files: self.wcc_output.files.clone(),
+            project_metrics: self.wcc_output.project.clone(),
+            complex_files_cyclomatic: self.get_complex_files(Complexity::Cyclomatic),
+            complex_files_cognitive: self.get_complex_files(Complexity::Cognitive),
+            ignored_files: self.wcc_output.ignored_files.clone(),
+        }
+    }
+}
+
+impl WccPrinter for JsonPrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let output = self.format_output();
+        let json = serde_json::to_string(&output)?;
+        fs::write(self.output_path, json.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct ComplexSpaces {
+    complex_cyclomatic: usize,
+    not_complex_cyclomatic: usize,
+    complex_cognitive: usize,
+    not_complex_cognitive: usize,
+}
+
+// Line of a source page.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SourceLine<'a> {
+    number: usize,
+    text: &'a str,
+    // Number of hits, absent for lines which are not instrumented.
+    hits: Option<i32>,
+    // Functions starting at the line, from the outermost one.
+    functions: Vec<&'a FunctionMetrics>,
+    in_function: bool,
+}
+
+// Row of the html tables, embedded as json and rendered by the browser.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TableRow<'a> {
+    name: &'a str,
+    // Functions details page of a file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<&'a str>,
+    // Source page, at the first line of a function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    metrics: &'a Metrics,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    smells: &'a [Smell],
+    // Instrumented and uncovered lines of a function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instrumented_lines: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uncovered_lines_num: Option<usize>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    uncovered_lines: &'a [LineRange],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    targets: Option<&'a Targets>,
+}
+
+impl<'a> TableRow<'a> {
+    fn file((details, file, source): &'a FilePages<'a>) -> Self {
+        Self {
+            name: &file.name,
+            details: details.as_deref(),
+            source: source.clone(),
+            metrics: &file.metrics,
+            smells: &[],
+            instrumented_lines: None,
+            uncovered_lines_num: None,
+            uncovered_lines: &[],
+            targets: file.targets.as_ref(),
+        }
+    }
+
+    fn function(function: &'a FunctionMetrics, source: Option<&str>) -> Self {
+        Self {
+            name: &function.name,
+            details: None,
+            source: source.map(|source| format!("{source}#L{}", function.start_line)),
+            metrics: &function.metrics,
+            smells: &function.smells,
+            instrumented_lines: Some(function.instrumented_lines),
+            uncovered_lines_num: Some(
+                function
+                    .instrumented_lines
+                    .saturating_sub(function.covered_lines),
+            ),
+            uncovered_lines: &function.uncovered_lines,
+            targets: function.targets.as_ref(),
+        }
+    }
+}
+
+// Html pages of a file: functions details and source.
+pub(crate) type FilePages<'a> = (Option<String>, &'a FileMetrics, Option<String>);
+
+// Html page rendered into a file name, relative to the html output.
+type Page = (String, String);
+
+// Third-party asset inlined in the single file output.
+#[derive(Serialize)]
+struct InlineAsset {
+    mime: &'static str,
+    content: String,
+}
+
+pub(crate) struct HtmlPrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) project_path: &'a Path,
+    pub(crate) files: &'a [PathBuf],
+    pub(crate) grcov: &'a Grcov,
+    pub(crate) mode: Mode,
+    pub(crate) thresholds: MetricsThresholds,
+    pub(crate) templates_dir: Option<&'a Path>,
+    pub(crate) history: Option<&'a History>,
+    pub(crate) single: bool,
+    #[cfg(feature = "offline-html")]
+    pub(crate) offline: bool,
+}
+
+impl HtmlPrinter<'_> {
+    // Environment loading the templates from the templates directory,
+    // falling back to the built-in ones. The whole output is available
+    // to every template as the `wcc_output` global.
+    fn get_environment(&self) -> Environment<'static> {
+        let templates_dir = self.templates_dir.map(Path::to_path_buf);
+        let mut env = Environment::new();
+        env.set_loader(move |name| {
+            if let Some(path) = templates_dir
+                .as_ref()
+                .map(|dir| dir.join(name))
+                .filter(|path| path.is_file())
+            {
+                return fs::read_to_string(&path).map(Some).map_err(|error| {
+                    minijinja::Error::new(
+                        ErrorKind::TemplateNotFound,
+                        format!("cannot read template {}", path.display()),
+                    )
+                    .with_source(error)
+                });
+            }
+
+            Ok(TEMPLATES
+                .iter()
+                .find(|(template_name, _)| *template_name == name)
+                .map(|(_, source)| (*source).to_owned()))
+        });
+        env.add_global("wcc_output", Value::from_serialize(self.wcc_output));
+
+        env
+    }
+
+    // Urls of the assets used by the templates. The vendored assets are inlined
+    // in the single file output, while in offline mode they are written into
+    // the output directory and referenced relatively.
+    fn get_assets(&self) -> Result<HashMap<&'static str, String>> {
+        #[cfg(feature = "offline-html")]
+        if self.single {
+            return Ok(ASSETS
+                .iter()
+                .map(|(name, _, _)| (*name, format!("{INLINE_ASSET_PREFIX}{name}")))
+                .collect());
+        }
+
+        #[cfg(feature = "offline-html")]
+        if self.offline {
+            let assets_dir = self.output_path.join(ASSETS_DIR);
+            fs::create_dir_all(&assets_dir)?;
+            let mut assets = HashMap::new();
+            for ((name, _, file), content) in ASSETS.iter().zip(VENDORED_ASSETS) {
+                fs::write(assets_dir.join(file), content)?;
+                assets.insert(*name, format!("{ASSETS_DIR}/{file}"));
+            }
+
+            return Ok(assets);
+        }
+
+        Ok(ASSETS
+            .iter()
+            .map(|(name, url, _)| (*name, (*url).to_owned()))
+            .collect())
+    }
+
+    // Vendored assets inlined in the single file output.
+    fn get_inline_assets(&self) -> BTreeMap<&'static str, InlineAsset> {
+        #[cfg(feature = "offline-html")]
+        return ASSETS
+            .iter()
+            .zip(VENDORED_ASSETS)
+            .map(|((name, _, file), content)| {
+                let mime = if file.ends_with(".css") {
+                    "text/css"
+                } else {
+                    "text/javascript"
+                };
+                let content = String::from_utf8_lossy(content).into_owned();
+                (*name, InlineAsset { mime, content })
+            })
+            .collect();
+
+        #[cfg(not(feature = "offline-html"))]
+        BTreeMap::new()
+    }
+
+    fn format_files(&self, sources: &HashMap<&str, (&Path, &[Option<i32>])>) -> Vec<FilePages<'_>> {
+        self.wcc_output
+            .files
+            .iter()
+            .enumerate()
+            .map(|(file_number, file)| {
+                let details = file
+                    .functions
+                    .as_ref()
+                    .map(|_| format!("file_{}.html", file_number + 1));
+                let source = sources
+                    .contains_key(file.name.as_str())
+                    .then(|| format!("source_{}.html", file_number + 1));
+                (details, file, source)
+            })
+            .collect()
+    }
+
+    fn get_complex_files(&self) -> ComplexSpaces {
+        let mut complex_spaces = ComplexSpaces::default();
+        self.wcc_output.files.iter().for_each(|f| {
+            if f.metrics.cyclomatic.is_complex {
+                complex_spaces.complex_cyclomatic += 1;
+            };
+            if f.metrics.cognitive.is_complex {
+                complex_spaces.complex_cognitive += 1;
+            };
+        });
+        complex_spaces.not_complex_cyclomatic =
+            self.wcc_output.files.len() - complex_spaces.complex_cyclomatic;
+        complex_spaces.not_complex_cognitive =
+            self.wcc_output.files.len() - complex_spaces.complex_cognitive;
+
+        complex_spaces
+    }
+
+    fn get_complex_functions(&self, file: &FileMetrics) -> ComplexSpaces {
+        let mut complex_spaces = ComplexSpaces::default();
+        if let Some(functions) = &file.functions {
+            functions.iter().for_each(|f| {
+                if f.metrics.cyclomatic.is_complex {
+                    complex_spaces.complex_cyclomatic += 1;
+                };
+                if f.metrics.cognitive.is_complex {
+                    complex_spaces.complex_cognitive += 1;
+                };
+            });
+            complex_spaces.not_complex_cyclomatic =
+                functions.len() - complex_spaces.complex_cyclomatic;
+            complex_spaces.not_complex_cognitive =
+                functions.len() - complex_spaces.complex_cognitive;
+        }
+
+        complex_spaces
+    }
+
+    fn render_file_details(
+        &self,
+        env: &Environment,
+        files: &[FilePages],
+        assets: &HashMap<&str, String>,
+        pages: &mut Vec<Page>,
+    ) -> Result<()> {
+        let file_template = env.get_template(FILE_DETAILS.0)?;
+        for f in files {
+            if let Some(html) = &f.0 {
+                let complex_functions = self.get_complex_functions(f.1);
+                let file_output = file_template.render(context! {
+                    file => f.1,
+                    not_complex_cyclomatic => complex_functions.not_complex_cyclomatic,
+                    complex_cyclomatic => complex_functions.complex_cyclomatic,
+                    not_complex_cognitive => complex_functions.not_complex_cognitive,
+                    complex_cognitive => complex_functions.complex_cognitive,
+                    mode => "Functions",
+                    thresholds => self.thresholds,
+                    navbar_brand_href => "index.html",
+                    assets => assets,
+                    source => f.2,
+                    table_rows => f.1.functions.iter().flatten()
+                        .map(|function| TableRow::function(function, f.2.as_deref()))
+                        .collect::<Vec<_>>(),
+                })?;
+                pages.push((html.clone(), file_output));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_sources(
+        &self,
+        env: &Environment,
+        files: &[FilePages],
+        sources: &HashMap<&str, (&Path, &[Option<i32>])>,
+        assets: &HashMap<&str, String>,
+        pages: &mut Vec<Page>,
+    ) -> Result<()> {
+        let source_template = env.get_template(SOURCE.0)?;
+        for (details, file, source) in files {
+            let (Some(html), Some((path, lines_coverage))) =
+                (source, sources.get(file.name.as_str()))
+            else {
+                continue;
+            };
+            let text = String::from_utf8_lossy(&fs::read(path)?).into_owned();
+            let source_output = source_template.render(context! {
+                file => file,
+                lines => get_source_lines(&text, lines_coverage, file.functions.as_deref()),
+                details => details,
+                thresholds => self.thresholds,
+                navbar_brand_href => "index.html",
+                assets => assets,
+            })?;
+            pages.push((html.clone(), source_output));
+        }
+
+        Ok(())
+    }
+
+    // Writes all the pages into a single index page, which shows them
+    // through client-side routing.
+    fn print_single(&self, env: &Environment, pages: Vec<Page>) -> Result<()> {
+        let output = env.get_template(SINGLE.0)?.render(context! {
+            pages => pages.into_iter().collect::<BTreeMap<_, _>>(),
+            index => OUTPUT_HTML_INDEX,
+            inline_assets => self.get_inline_assets(),
+            inline_asset_prefix => INLINE_ASSET_PREFIX,
+        })?;
+        fs::write(self.output_path.join(OUTPUT_HTML_INDEX), output)?;
+
+        Ok(())
+    }
+}
+
+// Lines of a source file, with their coverage and the functions starting at them.
+fn get_source_lines<'a>(
+    text: &'a str,
+    lines_coverage: &[Option<i32>],
+    functions: Option<&'a [FunctionMetrics]>,
+) -> Vec<SourceLine<'a>> {
+    let mut functions: Vec<_> = functions.unwrap_or_default().iter().collect();
+    functions.sort_by_key(|function| (function.start_line, Reverse(function.end_line)));
+
+    text.lines()
+        .enumerate()
+        .map(|(index, text)| {
+            let number = index + 1;
+            SourceLine {
+                number,
+                text,
+                hits: lines_coverage.get(index).copied().flatten(),
+                functions: functions
+                    .iter()
+                    .filter(|function| function.start_line == number)
+                    .copied()
+                    .collect(),
+                in_function: functions
+                    .iter()
+                    .any(|function| (function.start_line..=function.end_line).contains(&number)),
+            }
+        })
+        .collect()
+}
+
+impl WccPrinter for HtmlPrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let env = self.get_environment();
+        let assets = self.get_assets()?;
+        let sources = get_sources(self.wcc_output, self.project_path, self.files, self.grcov);
+        let files = self.format_files(&sources);
+        let mut pages = Vec::new();
+        if let Mode::Functions = self.mode {
+            self.render_file_details(&env, &files, &assets, &mut pages)?;
+        }
+        self.render_sources(&env, &files, &sources, &assets, &mut pages)?;
+
+        let template = env.get_template(FILES.0)?;
+        let complex_files = self.get_complex_files();
+        let output = template.render(context! {
+            treemap => get_treemap(&files, &sources, self.thresholds),
+            scatter => get_scatter(&files, self.mode, self.thresholds),
+            trend => self.history.map(get_trend),
+            table_rows => files.iter().map(TableRow::file).collect::<Vec<_>>(),
+            files => files,
+            ignored_files => self.wcc_output.ignored_files,
+            ignored_files_num => self.wcc_output.ignored_files.len(),
+            not_complex_cyclomatic => complex_files.not_complex_cyclomatic,
+            complex_cyclomatic => complex_files.complex_cyclomatic,
+            not_complex_cognitive => complex_files.not_complex_cognitive,
+            complex_cognitive => complex_files.complex_cognitive,
+            project => self.wcc_output.project,
+            mode => self.mode,
+            thresholds => self.thresholds,
+            assets => assets,
+        })?;
+        pages.push((OUTPUT_HTML_INDEX.to_owned(), output));
+
+        if self.single {
+            return self.print_single(&env, pages);
+        }
+        for (name, html) in pages {
+            fs::write(self.output_path.join(name), html)?;
+        }
+
+        Ok(())
+    }
+}
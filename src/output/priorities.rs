@@ -0,0 +1,244 @@
+use std::io::Write;
+
+use crate::{
+    concurrent::{functions::FunctionMetrics, LineRange, WccOutput},
+    error::Result,
+    metrics::{
+        crap::{crap, CrapMetric},
+        round_sd,
+        wcc::wcc_complexity_threshold,
+        Metric, MetricsThresholds,
+    },
+    Complexity,
+};
+
+use super::{
+    table::{format_name, get_width, HEADER, MIN_NAME_WIDTH, VALUE_WIDTH, VIOLATION},
+    WccPrinter,
+};
+
+pub(crate) const DEFAULT_PRIORITIES_TOP: usize = 10;
+// Minimum width of the uncovered line ranges column.
+const MIN_LINES_WIDTH: usize = 20;
+const HEADERS: [&str; 5] = ["Uncovered", "Wcc gain", "CRAP", "CRAP gain", "Gain/line"];
+
+// Payoff of covering the uncovered lines of a function.
+struct Priority<'a> {
+    file: &'a str,
+    function: &'a FunctionMetrics,
+    uncovered: usize,
+    // Increase of the project Wcc, in percentage points.
+    wcc_gain: f64,
+    // Decrease of the function CRAP.
+    crap_gain: f64,
+}
+
+impl Priority<'_> {
+    #[inline]
+    fn gain_per_line(&self) -> f64 {
+        self.crap_gain / self.uncovered as f64
+    }
+}
+
+// Estimates, for each function with uncovered lines, how much the project Wcc
+// and its own CRAP would improve if they became covered, computed with the cyclomatic
+// complexity. Functions are ranked by CRAP gain per uncovered line, then by Wcc gain.
+fn get_priorities(wcc_output: &WccOutput) -> Vec<Priority<'_>> {
+    let project_lines: usize = wcc_output
+        .files
+        .iter()
+        .map(|file| file.instrumented_lines)
+        .sum();
+    let mut priorities: Vec<_> = wcc_output
+        .files
+        .iter()
+        .flat_map(|file| {
+            file.functions
+                .iter()
+                .flatten()
+                .map(move |function| (file.name.as_str(), function))
+        })
+        .filter_map(|(file, function)| {
+            let uncovered = function.instrumented_lines - function.covered_lines;
+            if uncovered == 0 {
+                return None;
+            }
+            let data = &function.metrics.cyclomatic;
+            // Spaces above the complexity limit do not contribute to Wcc.
+            let wcc_gain = if data.complexity <= wcc_complexity_threshold(Complexity::Cyclomatic) {
+                round_sd(uncovered as f64 / project_lines as f64 * 100.0)
+            } else {
+                0.0
+            };
+
+            Some(Priority {
+                file,
+                function,
+                uncovered,
+                wcc_gain,
+                crap_gain: round_sd(data.crap - crap(1.0, data.complexity)),
+            })
+        })
+        .collect();
+
+    priorities.sort_by(|a, b| {
+        b.gain_per_line()
+            .total_cmp(&a.gain_per_line())
+            .then_with(|| b.wcc_gain.total_cmp(&a.wcc_gain))
+            .then_with(|| a.file.cmp(b.file))
+            .then_with(|| a.function.start_line.cmp(&b.function.start_line))
+    });
+
+    priorities
+}
+
+// Formats line ranges as `3-5, 9`, omitting the ones which do not fit the width.
+fn format_line_ranges(ranges: &[LineRange], width: usize) -> String {
+    let mut output = String::new();
+    for (index, range) in ranges.iter().enumerate() {
+        let range = if range.start == range.end {
+            range.start.to_string()
+        } else {
+            format!("{}-{}", range.start, range.end)
+        };
+        let part = if index == 0 {
+            range
+        } else {
+            format!(", {range}")
+        };
+        // Room for the ellipsis replacing the omitted ranges.
+        let ellipsis = if index + 1 < ranges.len() { 3 } else { 0 };
+        if index > 0 && output.len() + part.len() + ellipsis > width {
+            output.push_str(", …");
+            break;
+        }
+        output.push_str(&part);
+    }
+
+    output
+}
+
+pub(crate) struct PrioritiesPrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) top: usize,
+    pub(crate) thresholds: MetricsThresholds,
+}
+
+impl PrioritiesPrinter<'_> {
+    fn format_output(&self, width: usize) -> String {
+        let priorities = get_priorities(self.wcc_output);
+        let name_width = width
+            .saturating_sub(VALUE_WIDTH * HEADERS.len() + MIN_LINES_WIDTH)
+            .max(MIN_NAME_WIDTH);
+        let lines_width = width
+            .saturating_sub(name_width + VALUE_WIDTH * HEADERS.len() + 1)
+            .max(MIN_LINES_WIDTH);
+
+        let mut output = format!(
+            "{HEADER}{:<name_width$}{} {}{HEADER:#}\n",
+            "Function",
+            HEADERS
+                .iter()
+                .map(|header| format!("{header:>VALUE_WIDTH$}"))
+                .collect::<String>(),
+            "Uncovered lines"
+        );
+        let top = &priorities[..priorities.len().min(self.top)];
+        for priority in top {
+            let function = priority.function;
+            let name = format_name(priority.file, Some(&function.name), name_width - 1);
+            let crap = format!("{:>VALUE_WIDTH$}", function.metrics.cyclomatic.crap);
+            let crap = if CrapMetric(self.thresholds)
+                .exceeds_threshold(function.metrics.cyclomatic.crap, Complexity::Cyclomatic)
+            {
+                format!("{VIOLATION}{crap}{VIOLATION:#}")
+            } else {
+                crap
+            };
+            output.push_str(&format!(
+                "{name:<name_width$}{:>VALUE_WIDTH$}{:>VALUE_WIDTH$}{crap}{:>VALUE_WIDTH$}{:>VALUE_WIDTH$} {}\n",
+                priority.uncovered,
+                format!("+{}%", priority.wcc_gain),
+                format!("-{}", priority.crap_gain),
+                round_sd(priority.gain_per_line()),
+                format_line_ranges(&function.uncovered_lines, lines_width),
+            ));
+        }
+
+        let project_wcc = self.wcc_output.project.total.cyclomatic.wcc;
+        let wcc_gain: f64 = top.iter().map(|priority| priority.wcc_gain).sum();
+        output.push_str(&format!(
+            "\nCovering these {} of {} functions with uncovered lines raises the project Wcc from {project_wcc}% to about {}% (cyclomatic)\n",
+            top.len(),
+            priorities.len(),
+            round_sd((project_wcc + wcc_gain).min(100.0)),
+        ));
+
+        output
+    }
+}
+
+impl WccPrinter for PrioritiesPrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let output = self.format_output(get_width());
+        // Colors are stripped when stdout is not a terminal or `NO_COLOR` is set.
+        anstream::stdout().lock().write_all(output.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::Path;
+
+    use super::{format_line_ranges, PrioritiesPrinter};
+    use crate::{concurrent::LineRange, GrcovFile, Mode, WccRunner};
+
+    const PROJECT_PATH: &str = "./tests/seahorse/";
+    const COVDIR_PATH: &str = "./tests/seahorse/covdir.json";
+
+    #[test]
+    fn test_priorities() {
+        let wcc_output = WccRunner::new()
+            .mode(Mode::Functions)
+            .run(
+                Path::new(PROJECT_PATH),
+                GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+            )
+            .unwrap();
+        let output = PrioritiesPrinter {
+            wcc_output: &wcc_output,
+            top: 5,
+            thresholds: Default::default(),
+        }
+        .format_output(120);
+
+        insta::assert_snapshot!(anstream::adapter::strip_str(&output).to_string(), @r###"
+        Function                                       Uncovered   Wcc gain       CRAP  CRAP gain  Gain/line Uncovered lines
+        …amples/multiple_app.rs hello_action(23, 51)          21        +0%        272       -256       12.2 25-51
+        src/error.rs fmt(14, 22)                               6      +0.7%         42        -36          6 16-22
+        src/error.rs description(26, 34)                       6      +0.7%         42        -36          6 28-34
+        src/app.rs <anonymous>(236, 239)                       1      +0.1%        8.1       -3.1        3.1 238
+        examples/multiple_app.rs add_action(73, 76)            2      +0.2%          6         -4          2 75-76
+
+        Covering these 5 of 35 functions with uncovered lines raises the project Wcc from 64.8% to about 66.5% (cyclomatic)
+        "###);
+    }
+
+    #[test]
+    fn test_line_ranges() {
+        let ranges = [
+            LineRange { start: 3, end: 5 },
+            LineRange { start: 9, end: 9 },
+            LineRange { start: 12, end: 20 },
+        ];
+
+        assert_eq!(format_line_ranges(&ranges, 20), "3-5, 9, 12-20");
+        assert_eq!(format_line_ranges(&ranges, 10), "3-5, 9, …");
+        assert_eq!(format_line_ranges(&ranges, 1), "3-5, …");
+    }
+}
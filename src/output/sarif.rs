@@ -0,0 +1,211 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use serde::Serialize;
+
+use crate::{concurrent::WccOutput, error::Result, metrics::MetricsThresholds, Complexity, Mode};
+
+use super::{get_entities, get_violations, WccPrinter, CRAP_RULE_ID, SKUNK_RULE_ID, WCC_RULE_ID};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "weighted-code-coverage";
+
+const RULES: [(&str, &str); 3] = [
+    (
+        WCC_RULE_ID,
+        "Wcc is below the threshold: the code is poorly covered or too complex to be considered covered.",
+    ),
+    (
+        CRAP_RULE_ID,
+        "CRAP exceeds the threshold: the code is both complex and poorly covered.",
+    ),
+    (
+        SKUNK_RULE_ID,
+        "Skunk exceeds the threshold: the code is complex, smelly, and poorly covered.",
+    ),
+];
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [SarifRun<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct SarifRun<'a> {
+    tool: SarifTool,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+struct SarifDriver {
+    name: &'static str,
+    version: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    short_description: SarifMessage,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    rule_id: String,
+    level: &'static str,
+    message: SarifMessage,
+    locations: [SarifLocation<'a>; 1],
+    properties: SarifProperties<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation<'a> {
+    physical_location: SarifPhysicalLocation<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation<'a> {
+    artifact_location: SarifArtifactLocation<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation<'a> {
+    uri: &'a str,
+}
+
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    end_line: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifProperties<'a> {
+    name: &'a str,
+    complexity_metric: Complexity,
+    score: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threshold: Option<f64>,
+    coverage: f64,
+}
+
+pub(crate) struct SarifPrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) mode: Mode,
+    pub(crate) thresholds: MetricsThresholds,
+}
+
+impl SarifPrinter<'_> {
+    fn get_results(&self) -> Vec<SarifResult<'_>> {
+        let mut results = Vec::new();
+        for entity in get_entities(self.wcc_output, self.mode) {
+            for violation in get_violations(entity.metrics, self.thresholds) {
+                results.push(SarifResult {
+                    message: SarifMessage {
+                        text: violation.message(entity.name()),
+                    },
+                    level: "warning",
+                    locations: [SarifLocation {
+                        physical_location: SarifPhysicalLocation {
+                            artifact_location: SarifArtifactLocation { uri: entity.file },
+                            region: entity.lines().map(|(start_line, end_line)| SarifRegion {
+                                start_line,
+                                end_line,
+                            }),
+                        },
+                    }],
+                    properties: SarifProperties {
+                        name: entity.name(),
+                        complexity_metric: violation.complexity,
+                        score: violation.score,
+                        threshold: violation.threshold,
+                        coverage: entity.metrics.coverage,
+                    },
+                    rule_id: violation.rule_id,
+                });
+            }
+        }
+
+        // Sort results by location, since files are processed concurrently.
+        results.sort_by(|a, b| {
+            let a = &a.locations[0].physical_location;
+            let b = &b.locations[0].physical_location;
+            (a.artifact_location.uri, &a.region).cmp(&(b.artifact_location.uri, &b.region))
+        });
+
+        results
+    }
+}
+
+// Built-in rules, followed by the custom metrics which have results.
+fn get_rules(results: &[SarifResult]) -> Vec<SarifRule> {
+    let mut custom_ids: Vec<_> = results
+        .iter()
+        .map(|result| result.rule_id.as_str())
+        .filter(|id| RULES.iter().all(|(rule_id, _)| rule_id != id))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    custom_ids.sort_unstable();
+
+    RULES
+        .iter()
+        .map(|(id, description)| (*id, (*description).to_owned()))
+        .chain(
+            custom_ids
+                .into_iter()
+                .map(|id| (id, format!("The custom metric {id} crosses its threshold."))),
+        )
+        .map(|(id, description)| SarifRule {
+            id: id.to_owned(),
+            short_description: SarifMessage { text: description },
+        })
+        .collect()
+}
+
+impl WccPrinter for SarifPrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let results = self.get_results();
+        let sarif = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: [SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: TOOL_NAME,
+                        version: env!("CARGO_PKG_VERSION"),
+                        rules: get_rules(&results),
+                    },
+                },
+                results,
+            }],
+        };
+        fs::write(self.output_path, serde_json::to_string(&sarif)?)?;
+
+        Ok(())
+    }
+}
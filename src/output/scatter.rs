@@ -0,0 +1,138 @@
+use serde::Serialize;
+
+use crate::{
+    metrics::{crap::crap, invert, skunk::skunk, wcc::wcc_complexity_threshold, MetricsThresholds},
+    Complexity, Mode,
+};
+
+use super::FilePages;
+
+// Number of coverage steps of the threshold curves.
+const CURVE_STEPS: usize = 50;
+
+// Function, or file in files mode, drawn in the scatter plot.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScatterPoint<'a> {
+    name: &'a str,
+    file: &'a str,
+    coverage: f64,
+    cyclomatic: f64,
+    cognitive: f64,
+    href: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct CurvePoint {
+    x: f64,
+    y: f64,
+}
+
+// Complexities at which the scores reach their thresholds, as coverage grows.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThresholdCurves {
+    crap: Vec<CurvePoint>,
+    skunk: Vec<CurvePoint>,
+    // Complexity above which a space does not contribute to Wcc.
+    wcc_complexity: f64,
+}
+
+impl ThresholdCurves {
+    fn new(complexity: Complexity, thresholds: MetricsThresholds) -> Self {
+        let crap_threshold = thresholds.crap(complexity);
+        let skunk_threshold = thresholds.skunk(complexity);
+
+        Self {
+            crap: threshold_curve(crap_threshold, crap),
+            skunk: threshold_curve(skunk_threshold, |coverage, complexity| {
+                skunk(coverage, complexity, 0.0)
+            }),
+            wcc_complexity: wcc_complexity_threshold(complexity),
+        }
+    }
+}
+
+// Data of the complexity versus coverage scatter plot.
+#[derive(Serialize)]
+pub(crate) struct Scatter<'a> {
+    points: Vec<ScatterPoint<'a>>,
+    cyclomatic: ThresholdCurves,
+    cognitive: ThresholdCurves,
+}
+
+// Retrieves the functions, or the files in files mode, and the threshold curves.
+pub(crate) fn get_scatter<'a>(
+    files: &'a [FilePages<'a>],
+    mode: Mode,
+    thresholds: MetricsThresholds,
+) -> Scatter<'a> {
+    let points = files
+        .iter()
+        .flat_map(|(details, file, source)| -> Vec<ScatterPoint<'a>> {
+            match (mode, &file.functions) {
+                (Mode::Functions, Some(functions)) => functions
+                    .iter()
+                    .map(|function| ScatterPoint {
+                        name: &function.name,
+                        file: &file.name,
+                        coverage: function.metrics.coverage,
+                        cyclomatic: function.metrics.cyclomatic.complexity,
+                        cognitive: function.metrics.cognitive.complexity,
+                        href: details.as_deref(),
+                    })
+                    .collect(),
+                (Mode::Functions, None) => Vec::new(),
+                (Mode::Files, _) => vec![ScatterPoint {
+                    name: &file.name,
+                    file: &file.name,
+                    coverage: file.metrics.coverage,
+                    cyclomatic: file.metrics.cyclomatic.complexity,
+                    cognitive: file.metrics.cognitive.complexity,
+                    href: source.as_deref(),
+                }],
+            }
+        })
+        .collect();
+
+    Scatter {
+        points,
+        cyclomatic: ThresholdCurves::new(Complexity::Cyclomatic, thresholds),
+        cognitive: ThresholdCurves::new(Complexity::Cognitive, thresholds),
+    }
+}
+
+// Inverts a score, which grows with complexity, finding for each coverage
+// percentage the complexity at which the score reaches the threshold.
+// Scores are never lower than the complexity, so it is at most the threshold,
+// which is also used when the rounded scores do not hit it exactly.
+fn threshold_curve(threshold: f64, score: impl Fn(f64, f64) -> f64) -> Vec<CurvePoint> {
+    (0..=CURVE_STEPS)
+        .map(|step| {
+            let coverage = step as f64 / CURVE_STEPS as f64;
+            CurvePoint {
+                x: coverage * 100.0,
+                y: invert(0.0, threshold, |complexity| {
+                    score(coverage, complexity) >= threshold
+                })
+                .unwrap_or(threshold),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crap_curve_reaches_threshold() {
+        let curve = threshold_curve(16.0, crap);
+
+        // Uncovered code reaches CRAP 16 with complexity 3.53, covered code with 16,
+        // and complexities are rounded up to a tenth.
+        assert_eq!(curve.first().map(|point| point.y), Some(3.6));
+        assert_eq!(curve.last().map(|point| point.y), Some(16.0));
+        assert!(curve.windows(2).all(|points| points[0].y <= points[1].y));
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    concurrent::{Grcov, WccOutput},
+    error::Result,
+    metrics::MetricsThresholds,
+    Mode,
+};
+
+use super::{
+    escape_xml, get_entities, get_sources, get_violations, WccPrinter, CRAP_RULE_ID, SKUNK_RULE_ID,
+    WCC_RULE_ID,
+};
+
+const ENGINE_ID: &str = "weighted-code-coverage";
+const CLEAN_CODE_ATTRIBUTE: &str = "TESTED";
+const SOFTWARE_QUALITY: &str = "MAINTAINABILITY";
+const IMPACT_SEVERITY: &str = "MEDIUM";
+const COVERAGE_VERSION: &str = "1";
+
+// Minutes needed to cover a single independent path of a function.
+const EFFORT_MINUTES_PER_PATH: f64 = 10.0;
+
+const RULES: [(&str, &str, &str); 3] = [
+    (
+        WCC_RULE_ID,
+        "Wcc is too low",
+        "Wcc is below the threshold: the code is poorly covered or too complex to be considered covered.",
+    ),
+    (
+        CRAP_RULE_ID,
+        "CRAP is too high",
+        "CRAP exceeds the threshold: the code is both complex and poorly covered.",
+    ),
+    (
+        SKUNK_RULE_ID,
+        "Skunk is too high",
+        "Skunk exceeds the threshold: the code is complex, smelly, and poorly covered.",
+    ),
+];
+
+#[derive(Serialize)]
+struct SonarReport<'a> {
+    rules: Vec<SonarRule>,
+    issues: Vec<SonarIssue<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarRule {
+    id: String,
+    name: String,
+    description: String,
+    engine_id: &'static str,
+    clean_code_attribute: &'static str,
+    impacts: [SonarImpact; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarImpact {
+    software_quality: &'static str,
+    severity: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarIssue<'a> {
+    rule_id: String,
+    effort_minutes: u64,
+    primary_location: SonarLocation<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarLocation<'a> {
+    message: String,
+    file_path: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text_range: Option<SonarTextRange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarTextRange {
+    start_line: usize,
+    end_line: usize,
+}
+
+pub(crate) struct SonarIssuesPrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) mode: Mode,
+    pub(crate) thresholds: MetricsThresholds,
+}
+
+impl SonarIssuesPrinter<'_> {
+    fn get_issues(&self) -> Vec<SonarIssue<'_>> {
+        let mut entities = get_entities(self.wcc_output, self.mode);
+        // Sort entities by location, since files are processed concurrently.
+        entities.sort_by(|a, b| (a.file, a.lines()).cmp(&(b.file, b.lines())));
+
+        let mut issues = Vec::new();
+        for entity in entities {
+            for violation in get_violations(entity.metrics, self.thresholds) {
+                issues.push(SonarIssue {
+                    effort_minutes: effort_minutes(
+                        entity.metrics.cyclomatic.complexity,
+                        entity.metrics.coverage,
+                    ),
+                    primary_location: SonarLocation {
+                        message: violation.message(entity.name()),
+                        file_path: entity.file,
+                        text_range: entity.lines().map(|(start_line, end_line)| SonarTextRange {
+                            start_line,
+                            end_line,
+                        }),
+                    },
+                    rule_id: violation.rule_id,
+                });
+            }
+        }
+
+        issues
+    }
+}
+
+// Estimates the minutes needed to cover the uncovered independent paths.
+#[inline]
+fn effort_minutes(complexity: f64, coverage: f64) -> u64 {
+    let uncovered_paths = (complexity * (100.0 - coverage) / 100.0).ceil().max(1.0);
+
+    (uncovered_paths * EFFORT_MINUTES_PER_PATH) as u64
+}
+
+// Built-in rules, followed by the custom metrics which have issues.
+fn get_rules(issues: &[SonarIssue]) -> Vec<SonarRule> {
+    let mut custom_ids: Vec<_> = issues
+        .iter()
+        .map(|issue| issue.rule_id.as_str())
+        .filter(|id| RULES.iter().all(|(rule_id, _, _)| rule_id != id))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    custom_ids.sort_unstable();
+
+    RULES
+        .iter()
+        .map(|(id, name, description)| (*id, (*name).to_owned(), (*description).to_owned()))
+        .chain(custom_ids.into_iter().map(|id| {
+            (
+                id,
+                format!("{id} crosses its threshold"),
+                format!("The custom metric {id} crosses its threshold."),
+            )
+        }))
+        .map(|(id, name, description)| SonarRule {
+            id: id.to_owned(),
+            name,
+            description,
+            engine_id: ENGINE_ID,
+            clean_code_attribute: CLEAN_CODE_ATTRIBUTE,
+            impacts: [SonarImpact {
+                software_quality: SOFTWARE_QUALITY,
+                severity: IMPACT_SEVERITY,
+            }],
+        })
+        .collect()
+}
+
+impl WccPrinter for SonarIssuesPrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let issues = self.get_issues();
+        let report = SonarReport {
+            rules: get_rules(&issues),
+            issues,
+        };
+        let report_json = serde_json::to_string_pretty(&report)?;
+        fs::write(self.output_path, report_json)?;
+
+        Ok(())
+    }
+}
+
+pub(crate) struct SonarCoveragePrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) output_path: &'a Path,
+    pub(crate) project_path: &'a Path,
+    pub(crate) files: &'a [PathBuf],
+    pub(crate) grcov: &'a Grcov,
+}
+
+impl SonarCoveragePrinter<'_> {
+    fn format_coverage(&self) -> String {
+        // Only the analyzed files are exported, sorted by path.
+        let files_coverage: BTreeMap<_, _> =
+            get_sources(self.wcc_output, self.project_path, self.files, self.grcov)
+                .into_iter()
+                .map(|(name, (_, lines_coverage))| (name.replace('\\', "/"), lines_coverage))
+                .collect();
+
+        let mut coverage = format!("<coverage version=\"{COVERAGE_VERSION}\">\n");
+        for (name, lines_coverage) in files_coverage {
+            let _ = writeln!(coverage, "  <file path=\"{}\">", escape_xml(&name));
+            // Lines without coverage data do not need to be covered.
+            for (line, hits) in lines_coverage
+                .iter()
+                .enumerate()
+                .filter_map(|(index, hits)| Some((index + 1, (*hits)?)))
+            {
+                let _ = writeln!(
+                    coverage,
+                    "    <lineToCover lineNumber=\"{line}\" covered=\"{}\"/>",
+                    hits > 0
+                );
+            }
+            coverage.push_str("  </file>\n");
+        }
+        coverage.push_str("</coverage>\n");
+
+        coverage
+    }
+}
+
+impl WccPrinter for SonarCoveragePrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        fs::write(self.output_path, self.format_coverage())?;
+
+        Ok(())
+    }
+}
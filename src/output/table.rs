@@ -0,0 +1,258 @@
+use std::io::Write;
+
+use anstyle::{AnsiColor, Style};
+use terminal_size::{terminal_size, Width};
+
+use crate::{
+    concurrent::{Metrics, WccOutput},
+    error::Result,
+    metrics::{
+        crap::CrapMetric, round_sd, skunk::SkunkMetric, wcc::WccMetric, Metric, MetricsThresholds,
+    },
+    Complexity, Mode, Sort,
+};
+
+use super::{get_entities, sort_by_risk, WccPrinter};
+
+// Width used when the output is not a terminal and `COLUMNS` is not set.
+const DEFAULT_WIDTH: usize = 100;
+// Maximum number of files or functions shown in the table.
+const MAX_ROWS: usize = 25;
+pub(super) const MIN_NAME_WIDTH: usize = 20;
+const MIN_FILE_WIDTH: usize = 5;
+pub(super) const VALUE_WIDTH: usize = 11;
+const HEADERS: [&str; 5] = ["Coverage", "Wcc", "CRAP", "Skunk", "Complexity"];
+
+pub(super) const VIOLATION: Style = Style::new()
+    .fg_color(Some(anstyle::Color::Ansi(AnsiColor::Red)))
+    .bold();
+const COMPLEX: Style = Style::new().fg_color(Some(anstyle::Color::Ansi(AnsiColor::Yellow)));
+pub(super) const HEADER: Style = Style::new().bold().underline();
+
+pub(crate) struct TablePrinter<'a> {
+    pub(crate) wcc_output: &'a WccOutput,
+    pub(crate) mode: Mode,
+    pub(crate) sort_by: &'a Sort,
+    pub(crate) thresholds: MetricsThresholds,
+}
+
+impl TablePrinter<'_> {
+    // Formats the cyclomatic metrics, highlighting the ones crossing their thresholds.
+    fn format_values(&self, metrics: &Metrics) -> String {
+        let data = &metrics.cyclomatic;
+        let values = [
+            (format!("{}%", metrics.coverage), false),
+            (
+                data.wcc.to_string(),
+                WccMetric(self.thresholds).exceeds_threshold(data.wcc, Complexity::Cyclomatic),
+            ),
+            (
+                data.crap.to_string(),
+                CrapMetric(self.thresholds).exceeds_threshold(data.crap, Complexity::Cyclomatic),
+            ),
+            (
+                data.skunk.to_string(),
+                SkunkMetric(self.thresholds).exceeds_threshold(data.skunk, Complexity::Cyclomatic),
+            ),
+            (round_sd(data.complexity).to_string(), false),
+        ];
+
+        values
+            .iter()
+            .map(|(value, is_violation)| {
+                let value = format!("{value:>VALUE_WIDTH$}");
+                if *is_violation {
+                    format!("{VIOLATION}{value}{VIOLATION:#}")
+                } else {
+                    value
+                }
+            })
+            .collect()
+    }
+
+    fn format_output(&self, width: usize) -> String {
+        let project = &self.wcc_output.project.total;
+        let entities_name = match self.mode {
+            Mode::Files => "files",
+            Mode::Functions => "functions",
+        };
+        let mut entities = get_entities(self.wcc_output, self.mode);
+        sort_by_risk(&mut entities, self.sort_by);
+
+        let name_width = width
+            .saturating_sub(VALUE_WIDTH * HEADERS.len())
+            .max(MIN_NAME_WIDTH);
+        let name_header = match self.mode {
+            Mode::Files => "File",
+            Mode::Functions => "Function",
+        };
+
+        let mut output = format!(
+            "{HEADER}{:<name_width$}{}{HEADER:#}\n",
+            name_header,
+            HEADERS
+                .iter()
+                .map(|header| format!("{header:>VALUE_WIDTH$}"))
+                .collect::<String>()
+        );
+        for entity in entities.iter().take(MAX_ROWS) {
+            let name = format_name(
+                entity.file,
+                entity.function.map(|f| f.name.as_str()),
+                name_width - 1,
+            );
+            let name = format!("{name:<name_width$}");
+            let name = if entity.metrics.cyclomatic.is_complex {
+                format!("{COMPLEX}{name}{COMPLEX:#}")
+            } else {
+                name
+            };
+            output.push_str(&format!("{name}{}\n", self.format_values(entity.metrics)));
+        }
+        if entities.len() > MAX_ROWS {
+            output.push_str(&format!(
+                "... {} more {entities_name}\n",
+                entities.len() - MAX_ROWS
+            ));
+        }
+
+        let complex = entities
+            .iter()
+            .filter(|entity| entity.metrics.cyclomatic.is_complex)
+            .count();
+        output.push_str(&format!(
+            "\n{HEADER}{:<name_width$}{}{HEADER:#}\n",
+            "Project",
+            HEADERS
+                .iter()
+                .map(|header| format!("{header:>VALUE_WIDTH$}"))
+                .collect::<String>()
+        ));
+        output.push_str(&format!(
+            "{:<name_width$}{}\n",
+            "Total",
+            self.format_values(project)
+        ));
+        output.push_str(&format!(
+            "\nComplex {entities_name}: {complex} of {} (cyclomatic)\n",
+            entities.len()
+        ));
+
+        output
+    }
+}
+
+impl WccPrinter for TablePrinter<'_> {
+    type Output = Result<()>;
+
+    fn print(self) -> Self::Output {
+        let output = self.format_output(get_width());
+        // Colors are stripped when stdout is not a terminal or `NO_COLOR` is set.
+        anstream::stdout().lock().write_all(output.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+// Width of the terminal, falling back to the `COLUMNS` environment variable.
+#[inline]
+pub(super) fn get_width() -> usize {
+    terminal_size()
+        .map(|(Width(width), _)| width as usize)
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+// Formats the name of a file or function, shortening the file path
+// to fit the given width while keeping the function name whole, if possible.
+#[inline]
+pub(super) fn format_name(file: &str, function: Option<&str>, width: usize) -> String {
+    match function {
+        Some(function) => {
+            let file_width = width.saturating_sub(function.chars().count() + 1);
+            if file_width >= MIN_FILE_WIDTH {
+                format!("{} {function}", truncate_start(file, file_width))
+            } else {
+                truncate_start(&format!("{file} {function}"), width)
+            }
+        }
+        None => truncate_start(file, width),
+    }
+}
+
+// Truncates the start of a name, keeping its most specific part.
+#[inline]
+fn truncate_start(name: &str, width: usize) -> String {
+    let len = name.chars().count();
+    if len <= width {
+        return name.to_owned();
+    }
+
+    let tail: String = name.chars().skip(len - width + 1).collect();
+    format!("…{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::Path;
+
+    use super::TablePrinter;
+    use crate::{GrcovFile, Mode, Sort, WccRunner};
+
+    const PROJECT_PATH: &str = "./tests/seahorse/";
+    const COVDIR_PATH: &str = "./tests/seahorse/covdir.json";
+
+    #[test]
+    fn test_table() {
+        let wcc_output = WccRunner::new()
+            .mode(Mode::Functions)
+            .run(
+                Path::new(PROJECT_PATH),
+                GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+            )
+            .unwrap();
+        let output = TablePrinter {
+            wcc_output: &wcc_output,
+            mode: Mode::Functions,
+            sort_by: &Sort::Crap,
+            thresholds: Default::default(),
+        }
+        .format_output(100);
+
+        insta::assert_snapshot!(anstream::adapter::strip_str(&output).to_string(), @r###"
+        Function                                        Coverage        Wcc       CRAP      Skunk Complexity
+        …amples/multiple_app.rs hello_action(23, 51)          0%          0        272       42.7         16
+        src/app.rs command_help_text(329, 369)               16%         16       45.9       19.2          8
+        src/error.rs fmt(14, 22)                              0%          0         42         16          6
+        src/error.rs description(26, 34)                      0%          0         42         16          6
+        src/flag.rs value(125, 147)                        73.3%       73.3       19.3       21.7         15
+        src/command.rs flag_help_text(135, 202)            76.3%       76.3       16.6       19.5         14
+        src/app.rs flag_help_text(260, 327)                84.2%       84.2       14.8       17.7         14
+        src/command.rs <anonymous>(145, 168)               62.5%       62.5       13.3       14.6          9
+        src/app.rs run(198, 230)                           81.8%       81.8       11.7       14.3         11
+        src/app.rs <anonymous>(270, 293)                    100%        100          9          9          9
+        src/app.rs <anonymous>(419, 433)                     70%         70        8.3       10.5          7
+        src/app.rs <anonymous>(486, 500)                     70%         70        8.3       10.5          7
+        src/app.rs <anonymous>(534, 548)                     70%         70        8.3       10.5          7
+        src/app.rs <anonymous>(581, 595)                     70%         70        8.3       10.5          7
+        src/app.rs <anonymous>(236, 239)                     50%         50        8.1        9.2          5
+        src/app.rs select_command(234, 242)                 100%        100          8          8          8
+        src/app.rs multiple_app_test(418, 482)              100%        100          8          8          8
+        src/app.rs single_app_test(485, 530)                100%        100          8          8          8
+        src/app.rs flag_only_app_test(533, 577)             100%        100          8          8          8
+        …rs single_app_equal_notation_test(580, 622)        100%        100          8          8          8
+        src/command.rs run(124, 133)                       57.1%       57.1          7        8.6          5
+        src/context.rs new(18, 51)                         90.5%       90.5          7        8.1          7
+        src/context.rs result_flag_value(54, 67)            100%        100          7          7          7
+        src/flag.rs option_index(115, 122)                  100%        100          7          7          7
+        src/app.rs command(135, 148)                       45.5%       45.5        6.6        7.6          4
+        ... 45 more functions
+
+        Project                                         Coverage        Wcc       CRAP      Skunk Complexity
+        Total                                              77.2%       64.8        9.4       11.8        8.5
+
+        Complex functions: 19 of 70 (cyclomatic)
+        "###);
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::{
+    concurrent::{files::FileMetrics, Metrics},
+    metrics::MetricsThresholds,
+    Complexity,
+};
+
+use super::{FilePages, Rating};
+
+// Size of the treemap, in SVG user units.
+const WIDTH: f64 = 1200.0;
+const HEIGHT: f64 = 600.0;
+
+// Space reserved to the name of a directory, and padding around its children.
+const HEADER: f64 = 16.0;
+const PADDING: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Rect {
+    // Removes the directory header and padding from the rectangle,
+    // when there is room for them.
+    fn inner(self) -> Self {
+        let header = if self.height > 2.0 * HEADER {
+            HEADER
+        } else {
+            0.0
+        };
+        let padding = if self.width > 4.0 * PADDING && self.height > header + 4.0 * PADDING {
+            PADDING
+        } else {
+            0.0
+        };
+
+        Rect {
+            x: self.x + padding,
+            y: self.y + header + padding,
+            width: self.width - 2.0 * padding,
+            height: self.height - header - 2.0 * padding,
+        }
+    }
+}
+
+// Ratings of Wcc and CRAP of a file.
+#[derive(Serialize)]
+struct Ratings {
+    wcc: Rating,
+    crap: Rating,
+}
+
+impl Ratings {
+    fn new(metrics: &Metrics, complexity: Complexity, thresholds: MetricsThresholds) -> Self {
+        let data = match complexity {
+            Complexity::Cognitive => &metrics.cognitive,
+            _ => &metrics.cyclomatic,
+        };
+
+        Self {
+            wcc: Rating::wcc(data.wcc, thresholds),
+            crap: Rating::crap(data.crap, complexity, thresholds),
+        }
+    }
+}
+
+// File drawn in the treemap, linked to its details or source page.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TreemapFile<'a> {
+    href: Option<&'a str>,
+    metrics: &'a Metrics,
+    cyclomatic: Ratings,
+    cognitive: Ratings,
+}
+
+// Rectangle of a file or of a directory.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TreemapRect<'a> {
+    name: String,
+    path: String,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    ploc: usize,
+    // Absent for directories.
+    file: Option<TreemapFile<'a>>,
+}
+
+// Node of the directory hierarchy.
+#[derive(Default)]
+struct Node<'a> {
+    ploc: usize,
+    file: Option<(Option<&'a str>, &'a FileMetrics)>,
+    children: BTreeMap<String, Node<'a>>,
+}
+
+impl<'a> Node<'a> {
+    fn insert(
+        &mut self,
+        components: &[String],
+        ploc: usize,
+        file: (Option<&'a str>, &'a FileMetrics),
+    ) {
+        self.ploc += ploc;
+        match components {
+            [] => self.file = Some(file),
+            [name, rest @ ..] => self
+                .children
+                .entry(name.clone())
+                .or_default()
+                .insert(rest, ploc, file),
+        }
+    }
+
+    // Merges directories having a single child directory, so that
+    // their chain is drawn as a single rectangle.
+    fn compact(mut self) -> Self {
+        self.children = std::mem::take(&mut self.children)
+            .into_iter()
+            .map(|(mut name, mut child)| {
+                while child.file.is_none() && child.children.len() == 1 {
+                    let Some((grandchild_name, grandchild)) = child.children.pop_first() else {
+                        break;
+                    };
+                    if grandchild.file.is_some() {
+                        child.children.insert(grandchild_name, grandchild);
+                        break;
+                    }
+                    name = format!("{name}/{grandchild_name}");
+                    child = grandchild;
+                }
+                (name, child.compact())
+            })
+            .collect();
+
+        self
+    }
+}
+
+// Lays out the directory hierarchy of the files, sizing each file by its PLOC.
+pub(crate) fn get_treemap<'a>(
+    files: &'a [FilePages<'a>],
+    sources: &HashMap<&str, (&Path, &[Option<i32>])>,
+    thresholds: MetricsThresholds,
+) -> Vec<TreemapRect<'a>> {
+    let mut root = Node::default();
+    for (details, file, source) in files {
+        // PLOC are the lines having coverage data.
+        let Some(ploc) = sources
+            .get(file.name.as_str())
+            .map(|(_, lines_coverage)| lines_coverage.iter().flatten().count())
+            .filter(|ploc| *ploc > 0)
+        else {
+            continue;
+        };
+        let components: Vec<_> = Path::new(&file.name)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        root.insert(
+            &components,
+            ploc,
+            (details.as_deref().or(source.as_deref()), *file),
+        );
+    }
+
+    let mut rects = Vec::new();
+    let bounds = Rect {
+        x: 0.0,
+        y: 0.0,
+        width: WIDTH,
+        height: HEIGHT,
+    };
+    layout_children(&root.compact(), "", bounds, thresholds, &mut rects);
+
+    rects
+}
+
+fn layout_children<'a>(
+    node: &Node<'a>,
+    path: &str,
+    bounds: Rect,
+    thresholds: MetricsThresholds,
+    rects: &mut Vec<TreemapRect<'a>>,
+) {
+    let mut children: Vec<_> = node.children.iter().collect();
+    children.sort_by(|a, b| b.1.ploc.cmp(&a.1.ploc).then_with(|| a.0.cmp(b.0)));
+    let areas: Vec<_> = children
+        .iter()
+        .map(|(_, child)| child.ploc as f64)
+        .collect();
+
+    for ((name, child), rect) in children.into_iter().zip(squarify(&areas, bounds)) {
+        let child_path = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{path}/{name}")
+        };
+        rects.push(TreemapRect {
+            name: name.clone(),
+            path: child_path.clone(),
+            x: round(rect.x),
+            y: round(rect.y),
+            width: round(rect.width),
+            height: round(rect.height),
+            ploc: child.ploc,
+            file: child.file.map(|(href, file)| TreemapFile {
+                href,
+                metrics: &file.metrics,
+                cyclomatic: Ratings::new(&file.metrics, Complexity::Cyclomatic, thresholds),
+                cognitive: Ratings::new(&file.metrics, Complexity::Cognitive, thresholds),
+            }),
+        });
+        if !child.children.is_empty() {
+            layout_children(child, &child_path, rect.inner(), thresholds, rects);
+        }
+    }
+}
+
+// Rounds the coordinates to keep the html output compact.
+#[inline]
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+// Lays out the areas, sorted in descending order, with the squarified algorithm
+// by Bruls, Huizing and van Wijk, so that rectangles are as square as possible.
+fn squarify(areas: &[f64], bounds: Rect) -> Vec<Rect> {
+    let total: f64 = areas.iter().sum();
+    if total <= 0.0 || bounds.width <= 0.0 || bounds.height <= 0.0 {
+        return vec![
+            Rect {
+                width: 0.0,
+                height: 0.0,
+                ..bounds
+            };
+            areas.len()
+        ];
+    }
+
+    // Scale the areas to the bounds.
+    let scale = bounds.width * bounds.height / total;
+    let areas: Vec<_> = areas.iter().map(|area| area * scale).collect();
+
+    let mut rects = Vec::with_capacity(areas.len());
+    let mut free = bounds;
+    let mut start = 0;
+    while start < areas.len() {
+        // Grow the row along the shorter side while its aspect ratio improves.
+        let side = free.width.min(free.height);
+        let mut end = start + 1;
+        let mut ratio = worst_ratio(&areas[start..end], side);
+        while end < areas.len() {
+            let next_ratio = worst_ratio(&areas[start..=end], side);
+            if next_ratio > ratio {
+                break;
+            }
+            ratio = next_ratio;
+            end += 1;
+        }
+
+        let row_area: f64 = areas[start..end].iter().sum();
+        if free.width >= free.height {
+            let width = row_area / free.height;
+            let mut y = free.y;
+            for area in &areas[start..end] {
+                let height = area / width;
+                rects.push(Rect {
+                    x: free.x,
+                    y,
+                    width,
+                    height,
+                });
+                y += height;
+            }
+            free.x += width;
+            free.width -= width;
+        } else {
+            let height = row_area / free.width;
+            let mut x = free.x;
+            for area in &areas[start..end] {
+                let width = area / height;
+                rects.push(Rect {
+                    x,
+                    y: free.y,
+                    width,
+                    height,
+                });
+                x += width;
+            }
+            free.y += height;
+            free.height -= height;
+        }
+        start = end;
+    }
+
+    rects
+}
+
+// Worst aspect ratio of a row of areas laid along a side.
+#[inline]
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum: f64 = row.iter().sum();
+    let max = row.iter().copied().fold(f64::MIN, f64::max);
+    let min = row.iter().copied().fold(f64::MAX, f64::min);
+    let side = side * side;
+
+    (side * max / (sum * sum)).max(sum * sum / (side * min))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squarify_fills_bounds() {
+        let bounds = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 6.0,
+            height: 4.0,
+        };
+        let rects = squarify(&[6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0], bounds);
+
+        assert_eq!(rects.len(), 7);
+        let area: f64 = rects.iter().map(|rect| rect.width * rect.height).sum();
+        assert!((area - 24.0).abs() < 1e-9);
+        assert!(rects.iter().all(|rect| {
+            rect.x >= 0.0
+                && rect.y >= 0.0
+                && rect.x + rect.width <= 6.0 + 1e-9
+                && rect.y + rect.height <= 4.0 + 1e-9
+        }));
+        // The first row of the example in the paper has two squares.
+        assert_eq!(
+            rects[0],
+            Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 3.0,
+                height: 2.0
+            }
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+
+use crate::{
+    concurrent::MetricsData,
+    history::{format_timestamp, History},
+};
+
+#[derive(Serialize)]
+struct TrendScores {
+    wcc: f64,
+    crap: f64,
+    skunk: f64,
+}
+
+impl TrendScores {
+    // Total Wcc and average CRAP and Skunk, as in the badges.
+    const fn new(total: &MetricsData, average: &MetricsData) -> Self {
+        Self {
+            wcc: total.wcc,
+            crap: average.crap,
+            skunk: average.skunk,
+        }
+    }
+}
+
+// Project metrics of a run drawn in the trend chart.
+#[derive(Serialize)]
+pub(crate) struct TrendPoint<'a> {
+    name: String,
+    date: String,
+    commit: Option<&'a str>,
+    label: Option<&'a str>,
+    files: usize,
+    coverage: f64,
+    cyclomatic: TrendScores,
+    cognitive: TrendScores,
+}
+
+// Retrieves the project metrics of the runs stored in the history,
+// from the oldest to the most recent one.
+pub(crate) fn get_trend(history: &History) -> Vec<TrendPoint<'_>> {
+    history
+        .entries
+        .iter()
+        .map(|entry| {
+            let (total, average) = (&entry.project.total, &entry.project.average);
+            TrendPoint {
+                name: entry.name(),
+                date: format_timestamp(entry.timestamp),
+                commit: entry.commit.as_deref(),
+                label: entry.label.as_deref(),
+                files: entry.files.len(),
+                coverage: total.coverage,
+                cyclomatic: TrendScores::new(&total.cyclomatic, &average.cyclomatic),
+                cognitive: TrendScores::new(&total.cognitive, &average.cognitive),
+            }
+        })
+        .collect()
+}
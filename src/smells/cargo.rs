@@ -0,0 +1,107 @@
+use serde::Deserialize;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use crate::error::Result;
+
+use super::{Smell, Smells};
+
+const COMPILER_MESSAGE: &str = "compiler-message";
+
+#[derive(Debug, Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Diagnostic {
+    message: String,
+    code: Option<DiagnosticCode>,
+    spans: Vec<DiagnosticSpan>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiagnosticCode {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiagnosticSpan {
+    file_name: String,
+    line_start: usize,
+    is_primary: bool,
+}
+
+impl Smells {
+    // Parses the diagnostics emitted by `cargo --message-format=json`,
+    // one json object per line.
+    //
+    // Only diagnostics with a lint code and a primary span are considered
+    // code smells, so summaries such as "aborting due to previous error" are skipped.
+    pub(crate) fn from_cargo(json_path: &Path, project_path: &Path) -> Result<Smells> {
+        let mut smells = Smells::default();
+        for line in BufReader::new(File::open(json_path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let cargo_message: CargoMessage = serde_json::from_str(&line)?;
+            if cargo_message.reason != COMPILER_MESSAGE {
+                continue;
+            }
+
+            if let Some(Diagnostic {
+                message,
+                code: Some(DiagnosticCode { code }),
+                spans,
+            }) = cargo_message.message
+            {
+                if let Some(span) = spans.into_iter().find(|span| span.is_primary) {
+                    smells.insert(
+                        project_path,
+                        &span.file_name,
+                        Smell {
+                            rule: code,
+                            line: span.line_start,
+                            message,
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(smells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Smells;
+    use std::path::Path;
+
+    const CARGO_PATH: &str = "./tests/smells_files/clippy.json";
+
+    #[test]
+    fn test_cargo() {
+        let smells = Smells::from_cargo(Path::new(CARGO_PATH), Path::new("project/")).unwrap();
+
+        insta::with_settings!({sort_maps => true}, {
+            insta::assert_yaml_snapshot!(smells, @r###"
+            ---
+            project/src/app.rs:
+              - rule: "clippy::needless_return"
+                line: 87
+                message: "unneeded `return` statement"
+              - rule: "clippy::len_zero"
+                line: 212
+                message: length comparison to zero
+            "###);
+        });
+    }
+}
@@ -0,0 +1,42 @@
+pub(crate) mod cargo;
+pub(crate) mod sarif;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::resolve_path;
+
+/// Code smell reported by a linter.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Smell {
+    /// Rule which detected the smell.
+    pub rule: String,
+    /// Line of the smell.
+    pub line: usize,
+    /// Message of the smell.
+    pub message: String,
+}
+
+// Code smells of each file, using the path of the files as the key.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct Smells(pub(crate) HashMap<PathBuf, Vec<Smell>>);
+
+impl Smells {
+    #[inline]
+    pub(crate) fn get(&self, file: &Path) -> &[Smell] {
+        self.0.get(file).map_or(&[], Vec::as_slice)
+    }
+
+    #[inline]
+    fn insert(&mut self, project_path: &Path, file: &str, smell: Smell) {
+        self.0
+            .entry(resolve_path(project_path, file))
+            .or_default()
+            .push(smell);
+    }
+}
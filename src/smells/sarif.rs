@@ -0,0 +1,116 @@
+use serde::Deserialize;
+use std::{fs::File, io::BufReader, path::Path};
+
+use crate::error::Result;
+
+use super::{Smell, Smells};
+
+#[derive(Debug, Deserialize)]
+struct Sarif {
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifRun {
+    #[serde(default)]
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: Option<String>,
+    message: SarifMessage,
+    #[serde(default)]
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifMessage {
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: Option<SarifPhysicalLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+}
+
+impl Smells {
+    // Parses the results of a SARIF log, using the first physical location
+    // of each result as the position of the code smell.
+    pub(crate) fn from_sarif(sarif_path: &Path, project_path: &Path) -> Result<Smells> {
+        let sarif: Sarif = serde_json::from_reader(BufReader::new(File::open(sarif_path)?))?;
+        let mut smells = Smells::default();
+
+        for result in sarif.runs.into_iter().flat_map(|run| run.results) {
+            let location = result
+                .locations
+                .into_iter()
+                .find_map(|location| location.physical_location);
+            if let Some(SarifPhysicalLocation {
+                artifact_location,
+                region: Some(region),
+            }) = location
+            {
+                smells.insert(
+                    project_path,
+                    &artifact_location.uri,
+                    Smell {
+                        rule: result.rule_id.unwrap_or_default(),
+                        line: region.start_line,
+                        message: result.message.text.unwrap_or_default(),
+                    },
+                );
+            }
+        }
+
+        Ok(smells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Smells;
+    use std::path::Path;
+
+    const SARIF_PATH: &str = "./tests/smells_files/clippy.sarif";
+
+    #[test]
+    fn test_sarif() {
+        let smells = Smells::from_sarif(Path::new(SARIF_PATH), Path::new("project/")).unwrap();
+
+        insta::with_settings!({sort_maps => true}, {
+            insta::assert_yaml_snapshot!(smells, @r###"
+            ---
+            /abs/src/error.rs:
+              - rule: "clippy::new_without_default"
+                line: 10
+                message: "you should consider adding a `Default` implementation"
+            project/src/app.rs:
+              - rule: "clippy::needless_return"
+                line: 87
+                message: "unneeded `return` statement"
+            "###);
+        });
+    }
+}
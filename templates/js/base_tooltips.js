@@ -68,6 +68,39 @@ tippy('.files', {
     allowHTML: true
 });
 
+tippy('.treemap-info', {
+    content: `Files grouped by directory, sized by their <em>PLOC</em> and coloured by <em>Wcc</em> or <em>CRAP</em>:
+    <br><br>
+    <ul>
+        <li>- <b>Green</b>: the metric meets its threshold.</li>
+        <li>- <b>Yellow</b>: the metric is within twice the distance from its threshold.</li>
+        <li>- <b>Red</b>: the metric is farther from its threshold.</li>
+    </ul>`,
+    allowHTML: true
+});
+
+tippy('.scatter-info', {
+    content: `Complexity against coverage of each analyzed function, or file in <em>Files</em> mode.
+    <br><br>
+    <ul>
+        <li>- <b>CRAP</b> and <b>Skunk threshold</b>: points above the curves exceed the thresholds.</li>
+        <li>- <b>Wcc complexity limit</b>: points above the line do not contribute to <em>Wcc</em>.</li>
+    </ul>
+    <br>Click on a point to open its details.`,
+    allowHTML: true
+});
+
+tippy('.trend-info', {
+    content: `Project metrics of the runs stored in the history, from the oldest to the most recent one.
+    <br><br>
+    <ul>
+        <li>- <b>Coverage</b> and <b>Wcc</b>: total project percentages, on the left axis.</li>
+        <li>- <b>Average CRAP</b> and <b>Skunk</b>: average scores, on the right axis.</li>
+    </ul>
+    <br>Runs are named after their label, commit or date.`,
+    allowHTML: true
+});
+
 tippy('.file-table', {
     content: 'A file is considered <b>complex</b> if at least one of the following apply: <em>Wcc</em> below threshold, <em>CRAP</em> or <em>Skunk</em> above threshold.',
     allowHTML: true
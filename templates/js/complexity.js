@@ -31,9 +31,27 @@ function closeHamburger() {
 
 // Handle switch to cyclomatic complexity.
 function cyclomatic() {
-    chart.data.datasets[0].data[0] = notComplexCyclomatic;
-    chart.data.datasets[0].data[1] = complexCyclomatic;
-    chart.update();
+    // Source pages have no chart.
+    if (typeof chart !== "undefined") {
+        chart.data.datasets[0].data[0] = notComplexCyclomatic;
+        chart.data.datasets[0].data[1] = complexCyclomatic;
+        chart.update();
+    }
+
+    // Only the index page has the scatter plot.
+    if (typeof scatterComplexity !== "undefined") {
+        scatterComplexity("cyclomatic");
+    }
+
+    // Only the index page of runs with a history has the trend chart.
+    if (typeof trendComplexity !== "undefined") {
+        trendComplexity("cyclomatic");
+    }
+
+    // Source pages have no table.
+    if (typeof wccTable !== "undefined") {
+        wccTable.setComplexity("cyclomatic");
+    }
 
     var cyclomaticElements = document.getElementsByClassName("cyclomatic");
     for (let e of cyclomaticElements) {
@@ -50,9 +68,27 @@ function cyclomatic() {
 
 // Handle switch to cognitive complexity.
 function cognitive() {
-    chart.data.datasets[0].data[0] = notComplexCognitive;
-    chart.data.datasets[0].data[1] = complexCognitive;
-    chart.update();
+    // Source pages have no chart.
+    if (typeof chart !== "undefined") {
+        chart.data.datasets[0].data[0] = notComplexCognitive;
+        chart.data.datasets[0].data[1] = complexCognitive;
+        chart.update();
+    }
+
+    // Only the index page has the scatter plot.
+    if (typeof scatterComplexity !== "undefined") {
+        scatterComplexity("cognitive");
+    }
+
+    // Only the index page of runs with a history has the trend chart.
+    if (typeof trendComplexity !== "undefined") {
+        trendComplexity("cognitive");
+    }
+
+    // Source pages have no table.
+    if (typeof wccTable !== "undefined") {
+        wccTable.setComplexity("cognitive");
+    }
 
     var cognitiveElements = document.getElementsByClassName("cognitive");
     for (let e of cognitiveElements) {
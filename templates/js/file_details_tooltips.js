@@ -24,6 +24,17 @@ tippy('.file-metrics', {
     allowHTML: true
 });
 
+tippy('.targets', {
+    content: `
+    <ul> 
+        <li>- <b>CRAP</b>: minimum file <em>coverage</em> at which <em>CRAP</em> does not exceed the threshold.</li>
+        <li>- <b>Skunk</b>: minimum file <em>coverage</em> at which <em>Skunk</em> does not exceed the threshold.</li>
+        <li>- <b>Wcc</b>: lines to cover, in code spaces not too complex, for <em>Wcc</em> to reach the threshold.</li>
+    </ul>
+    When coverage is not enough, the complexity has to be lowered.`,
+    allowHTML: true
+});
+
 tippy('.functions', {
     content: `
     <ul> 
@@ -0,0 +1,244 @@
+/**
+ * Search, filter and sort of the metrics tables, whose rows are rendered
+ * from the json data embedded in the page.
+ */
+
+// Escapes the html special characters of a text.
+function escapeHtml(text) {
+    return String(text)
+        .replaceAll("&", "&amp;")
+        .replaceAll("<", "&lt;")
+        .replaceAll(">", "&gt;")
+        .replaceAll('"', "&quot;")
+        .replaceAll("'", "&#39;");
+}
+
+// Builds the matcher of a path filter: a glob when it contains `*` or `?`,
+// a case-insensitive substring otherwise.
+function pathMatcher(query) {
+    query = query.trim().toLowerCase();
+    if (query === "") {
+        return () => true;
+    }
+    if (!/[*?]/.test(query)) {
+        return (path) => path.toLowerCase().includes(query);
+    }
+    const pattern = query
+        .split("")
+        .map((c) => (c === "*" ? ".*" : c === "?" ? "." : c.replace(/[.+^${}()|[\]\\]/g, "\\$&")))
+        .join("");
+    const regex = new RegExp(`^${pattern}$`);
+    return (path) => regex.test(path.toLowerCase());
+}
+
+class WccTable {
+    // * `rows` - rows with `name` and `metrics`.
+    // * `nameCell` - renders the content of the first cell of a row.
+    // * `thresholds` - thresholds used to colour the metrics.
+    constructor(rows, nameCell, thresholds) {
+        this.rows = rows;
+        this.nameCell = nameCell;
+        this.thresholds = thresholds;
+        this.complexity = "cyclomatic";
+        this.matches = () => true;
+        this.onlyComplex = false;
+        this.rangeMetric = "complexity";
+        this.range = [-Infinity, Infinity];
+        this.sortKey = null;
+        this.sortAscending = true;
+
+        this.body = document.getElementById("table-body");
+        this.search = document.getElementById("table-search");
+        this.complexToggle = document.getElementById("table-only-complex");
+        this.rangeSelect = document.getElementById("table-range-metric");
+        this.rangeMin = document.getElementById("table-range-min");
+        this.rangeMax = document.getElementById("table-range-max");
+        this.rangeValue = document.getElementById("table-range-value");
+        this.count = document.getElementById("table-count");
+
+        this.search.addEventListener("input", () => {
+            this.matches = pathMatcher(this.search.value);
+            this.render();
+        });
+        this.complexToggle.addEventListener("change", () => {
+            this.onlyComplex = this.complexToggle.checked;
+            this.render();
+        });
+        this.rangeSelect.addEventListener("change", () => {
+            this.rangeMetric = this.rangeSelect.value;
+            this.resetRange();
+            this.render();
+        });
+        for (const slider of [this.rangeMin, this.rangeMax]) {
+            slider.addEventListener("input", () => {
+                let [min, max] = [Number(this.rangeMin.value), Number(this.rangeMax.value)];
+                if (min > max) {
+                    [min, max] = slider === this.rangeMin ? [min, min] : [max, max];
+                    this.rangeMin.value = min;
+                    this.rangeMax.value = max;
+                }
+                this.range = [min, max];
+                this.render();
+            });
+        }
+        document.querySelectorAll("th[data-sort]").forEach((header) => {
+            header.addEventListener("click", (event) => {
+                // Clicks on the info icons only open their tooltips.
+                if (event.target.closest(".icon.is-clickable")) {
+                    return;
+                }
+                this.sortBy(header.dataset.sort);
+            });
+        });
+
+        this.resetRange();
+        this.render();
+    }
+
+    // Value of a column of a row, according to the current complexity.
+    value(row, key) {
+        const data = row.metrics[this.complexity];
+        if (key === "name") {
+            return row.name;
+        }
+        if (key === "coverage") {
+            return row.metrics.coverage;
+        }
+        if (key.startsWith("custom:")) {
+            return data.custom[Number(key.slice(7))].score;
+        }
+        return data[key];
+    }
+
+    // Sets the bounds of the range sliders to the values of the range metric.
+    resetRange() {
+        const values = this.rows.map((row) => this.value(row, this.rangeMetric));
+        const min = Math.floor(Math.min(...values, 0));
+        const max = Math.ceil(Math.max(...values, 0));
+        const step = max - min > 100 ? 1 : 0.1;
+        for (const slider of [this.rangeMin, this.rangeMax]) {
+            slider.min = min;
+            slider.max = max;
+            slider.step = step;
+        }
+        this.rangeMin.value = min;
+        this.rangeMax.value = max;
+        this.range = [min, max];
+    }
+
+    sortBy(key) {
+        if (this.sortKey === key) {
+            this.sortAscending = !this.sortAscending;
+        } else {
+            this.sortKey = key;
+            // Names are sorted alphabetically, metrics from the highest value.
+            this.sortAscending = key === "name";
+        }
+        document.querySelectorAll("th[data-sort]").forEach((header) => {
+            header.classList.toggle("is-sorted", header.dataset.sort === key);
+            header.classList.toggle("is-ascending", header.dataset.sort === key && this.sortAscending);
+        });
+        this.render();
+    }
+
+    setComplexity(complexity) {
+        this.complexity = complexity;
+        this.resetRange();
+        this.render();
+    }
+
+    // Rows passing the filters, in the sort order.
+    visibleRows() {
+        const [min, max] = this.range;
+        const rows = this.rows.filter((row) => {
+            const value = this.value(row, this.rangeMetric);
+            return (
+                this.matches(row.name) &&
+                (!this.onlyComplex || row.metrics[this.complexity].isComplex) &&
+                value >= min &&
+                value <= max
+            );
+        });
+        if (this.sortKey !== null) {
+            const direction = this.sortAscending ? 1 : -1;
+            rows.sort((a, b) => {
+                const [x, y] = [this.value(a, this.sortKey), this.value(b, this.sortKey)];
+                return direction * (typeof x === "string" ? x.localeCompare(y) : x - y);
+            });
+        }
+        return rows;
+    }
+
+    crapThreshold() {
+        return this.complexity === "cyclomatic" ? this.thresholds.crapCyclomatic : this.thresholds.crapCognitive;
+    }
+
+    skunkThreshold() {
+        return this.complexity === "cyclomatic" ? this.thresholds.skunkCyclomatic : this.thresholds.skunkCognitive;
+    }
+
+    // Coverage needed by a row to pass the thresholds it crosses, according to the current complexity.
+    formatTargets(row) {
+        if (row.targets === undefined) {
+            return "";
+        }
+        const data = row.metrics[this.complexity];
+        const targets = row.targets[this.complexity];
+        const tooComplex = "lower complexity";
+        const hints = [];
+        if (data.crap > this.crapThreshold()) {
+            hints.push(`CRAP: ${targets.crapCoverage === undefined ? tooComplex : `${targets.crapCoverage}% coverage`}`);
+        }
+        if (data.skunk > this.skunkThreshold()) {
+            hints.push(`Skunk: ${targets.skunkCoverage === undefined ? tooComplex : `${targets.skunkCoverage}% coverage`}`);
+        }
+        if (data.wcc < this.thresholds.wcc) {
+            hints.push(`Wcc: ${targets.wccLines === undefined ? tooComplex : `${targets.wccLines} more covered line${targets.wccLines === 1 ? "" : "s"}`}`);
+        }
+        if (hints.length === 0) {
+            return "";
+        }
+        return `
+            <p class="is-size-7 has-text-grey" title="Coverage needed to pass the thresholds">
+              To pass ${hints.join(", ")}
+            </p>`;
+    }
+
+    formatRow(row) {
+        const data = row.metrics[this.complexity];
+        const crapThreshold = this.crapThreshold();
+        const skunkThreshold = this.skunkThreshold();
+        const coverageClass = row.metrics.coverage < 60 ? "danger" : "success";
+        const wccClass = data.wcc < this.thresholds.wcc ? "danger" : "success";
+        const crapClass = data.crap > crapThreshold ? "danger" : "success";
+        const skunkClass = data.skunk > skunkThreshold ? "danger" : "success";
+        const progress = (value, color) => `
+            <td class="has-text-centered is-vcentered is-hidden-touch">
+              <div class="progress-wrapper">
+                <progress class="progress is-medium mb-0 is-${color}" value="${value}" max="100">${value}%</progress>
+                <p class="progress-value has-text-grey-dark">${value}%</p>
+              </div>
+            </td>
+            <td class="has-text-weight-medium has-text-centered is-hidden-desktop is-vcentered has-text-${color}">${value}%</td>`;
+        const score = (value, color) =>
+            `<td class="has-text-${color} has-text-weight-medium has-text-centered is-vcentered">${value}</td>`;
+
+        return `
+          <tr class="${data.isComplex ? "complex" : "not-complex"}">
+            <td class="is-vcentered">${this.nameCell(row)}${this.formatTargets(row)}</td>
+            <td class="has-text-weight-medium has-text-centered is-vcentered">${data.complexity}</td>
+            ${progress(row.metrics.coverage, coverageClass)}
+            ${progress(data.wcc, wccClass)}
+            ${score(data.crap, crapClass)}
+            ${score(data.skunk, skunkClass)}
+            ${(data.custom || []).map((metric) => score(metric.score, metric.exceedsThreshold ? "danger" : "success")).join("")}
+          </tr>`;
+    }
+
+    render() {
+        const rows = this.visibleRows();
+        this.body.innerHTML = rows.map((row) => this.formatRow(row)).join("");
+        this.count.textContent = `${rows.length} of ${this.rows.length}`;
+        this.rangeValue.textContent = `${this.range[0]} – ${this.range[1]}`;
+    }
+}
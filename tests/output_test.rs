@@ -1,13 +1,19 @@
 use std::{env::temp_dir, path::Path};
 
-use insta::sorted_redaction;
-use weighted_code_coverage::{GrcovFile, Mode, WccRunner};
+use insta::{rounded_redaction, sorted_redaction};
+use weighted_code_coverage::{
+    Complexity, Error, GrcovFile, History, Metric, MetricInput, Mode, MutationsFile, SmellsFile,
+    Sort, Thresholds, WccReport, WccRunner, SCHEMA_VERSION,
+};
 
 const PROJECT_PATH: &str = "./tests/seahorse/";
 const SNAPSHOTS_PATH: &str = "./snapshots/output/";
 const COVERALLS_PATH: &str = "./tests/seahorse/coveralls.json";
 const COVDIR_PATH: &str = "./tests/seahorse/covdir.json";
+const CLIPPY_PATH: &str = "./tests/seahorse/clippy.json";
+const MUTANTS_PATH: &str = "./tests/mutations_files/outcomes.json";
 const JSON_OUTPUT: &str = "wcc.json";
+const JSON_SCHEMA_PATH: &str = "./schema/wcc.schema.json";
 
 #[test]
 fn test_output_coveralls_files() {
@@ -45,11 +51,728 @@ fn test_output_covdir_functions() {
     );
 }
 
+#[test]
+fn test_output_coveralls_files_hit_weighted() {
+    compare_runner(
+        WccRunner::new().hit_weighted(true).sort_by(Sort::HitCrap),
+        GrcovFile::Coveralls(Path::new(COVERALLS_PATH)),
+        "output_coveralls_files_hit_weighted",
+    );
+}
+
+#[test]
+fn test_output_covdir_files_complexities() {
+    compare_runner(
+        WccRunner::new().complexities(&[
+            Complexity::HalsteadEffort,
+            Complexity::HalsteadDifficulty,
+            Complexity::MaintainabilityIndex,
+            Complexity::Exits,
+            Complexity::Args,
+        ]),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_covdir_files_complexities",
+    );
+}
+
+#[test]
+fn test_output_complexities_thresholds() {
+    let thresholds: Thresholds = "60,10,10,exits=1,args=5".parse().unwrap();
+    assert_eq!(thresholds.to_string(), "60,10,10,exits=1");
+    assert!("60,10,10,exits".parse::<Thresholds>().is_err());
+    assert!("60,10,10,unknown=1".parse::<Thresholds>().is_err());
+
+    let complex_files = |thresholds: Thresholds| {
+        WccRunner::new()
+            .complexities(&[Complexity::Exits])
+            .thresholds(thresholds)
+            .run(
+                Path::new(PROJECT_PATH),
+                GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+            )
+            .unwrap()
+            .files
+            .iter()
+            .filter(|file| file.metrics.complexities[&Complexity::Exits].is_complex)
+            .count()
+    };
+    assert!(complex_files(thresholds) > complex_files(Thresholds::default()));
+}
+
+#[test]
+fn test_output_coveralls_functions_smells() {
+    compare_runner(
+        WccRunner::new()
+            .mode(Mode::Functions)
+            .smells_file(SmellsFile::Cargo(Path::new(CLIPPY_PATH)))
+            .sort_by(Sort::Skunk),
+        GrcovFile::Coveralls(Path::new(COVERALLS_PATH)),
+        "output_coveralls_functions_smells",
+    );
+}
+
+#[test]
+fn test_output_covdir_functions_mutations() {
+    compare_runner(
+        WccRunner::new()
+            .mode(Mode::Functions)
+            .mutations_file(MutationsFile::CargoMutants(Path::new(MUTANTS_PATH))),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_covdir_functions_mutations",
+    );
+}
+
+// Number of uncovered lines weighted by complexity.
+#[derive(Debug)]
+struct UncoveredComplexity;
+
+impl Metric for UncoveredComplexity {
+    fn name(&self) -> &str {
+        "uncoveredComplexity"
+    }
+
+    fn score(&self, input: &MetricInput) -> f64 {
+        ((input.ploc - input.covered_lines) * input.complexity).round()
+    }
+
+    fn exceeds_threshold(&self, score: f64, _complexity: Complexity) -> bool {
+        score > 100.0
+    }
+}
+
+#[test]
+fn test_output_covdir_functions_custom_metric() {
+    compare_runner(
+        WccRunner::new()
+            .mode(Mode::Functions)
+            .add_metric(UncoveredComplexity)
+            .sort_by(Sort::Custom("uncoveredComplexity".to_owned())),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_covdir_functions_custom_metric",
+    );
+}
+
+#[test]
+fn test_output_custom_sort_unregistered() {
+    let error = WccRunner::new()
+        .mode(Mode::Functions)
+        .add_metric(UncoveredComplexity)
+        .sort_by(Sort::Custom(String::from("uncovered")))
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Options(message) if message == r#"Sort metric "uncovered" is not a registered custom metric"#
+    ));
+}
+
+#[test]
+fn test_output_sarif_functions() {
+    let sarif = compare_json_report(
+        WccRunner::new()
+            .mode(Mode::Functions)
+            .add_metric(UncoveredComplexity),
+        |wcc_runner, path| wcc_runner.sarif_path(path),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_sarif_functions",
+    );
+    // Custom metrics with results are described by rules too.
+    let rules = sarif["runs"][0]["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap();
+    assert_eq!(rules.last().unwrap()["id"], "uncoveredComplexity");
+}
+
+#[test]
+fn test_output_markdown_functions() {
+    compare_report(
+        WccRunner::new()
+            .mode(Mode::Functions)
+            .sort_by(Sort::Crap)
+            .markdown_top(8)
+            .markdown_max_size(1200),
+        |wcc_runner, path| wcc_runner.markdown_path(path),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_markdown_functions",
+    );
+}
+
+#[test]
+fn test_output_markdown_max_size() {
+    let markdown = |top, max_size: Option<usize>| {
+        let markdown_path = temp_dir().join(format!(
+            "output_markdown_max_size_{top}_{}.md",
+            max_size.unwrap_or_default()
+        ));
+        let mut wcc_runner = WccRunner::new()
+            .mode(Mode::Functions)
+            .sort_by(Sort::Crap)
+            .markdown_path(&markdown_path)
+            .markdown_top(top);
+        if let Some(max_size) = max_size {
+            wcc_runner = wcc_runner.markdown_max_size(max_size);
+        }
+        wcc_runner
+            .run(
+                Path::new(PROJECT_PATH),
+                GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+            )
+            .unwrap();
+        std::fs::read_to_string(markdown_path).unwrap()
+    };
+    let summary = markdown(0, None);
+
+    // The summary is dropped too when it does not fit.
+    assert_eq!(markdown(8, Some(summary.len() - 1)), "");
+    // The table is dropped when none of its rows fits.
+    assert_eq!(markdown(8, Some(summary.len() + 100)), summary);
+
+    let max_size = summary.len() + 600;
+    let output = markdown(8, Some(max_size));
+    assert!(output.len() <= max_size);
+    let shown = output
+        .lines()
+        .filter(|line| line.starts_with("| `"))
+        .count();
+    assert!((1..8).contains(&shown));
+    assert!(output.contains(&format!("### Top {shown} riskiest functions by crap")));
+    assert!(output.ends_with(&format!("_{} more functions omitted._\n", 8 - shown)));
+}
+
+#[test]
+fn test_output_csv_functions() {
+    compare_report(
+        WccRunner::new().mode(Mode::Functions),
+        |wcc_runner, path| wcc_runner.csv_path(path),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_csv_functions",
+    );
+}
+
+#[test]
+fn test_output_csv_functions_complexities() {
+    let csv = run_report(
+        WccRunner::new()
+            .mode(Mode::Functions)
+            .complexities(&[Complexity::Args])
+            .add_metric(UncoveredComplexity),
+        |wcc_runner, path| wcc_runner.csv_path(path),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_csv_functions_complexities",
+    );
+
+    let mut lines = csv.lines();
+    let columns: Vec<_> = lines.next().unwrap().split(',').collect();
+    for column in [
+        "cognitive_mutation_crap",
+        "uncovered_lines",
+        "cyclomatic_crap_coverage_target",
+        "args_wcc",
+        "args_mutation_crap",
+        "cyclomatic_uncoveredComplexity",
+        "args_uncoveredComplexity_exceeds_threshold",
+    ] {
+        assert!(columns.contains(&column), "missing column {column}");
+    }
+    // Function names contain commas, so they are quoted.
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    assert!(reader
+        .records()
+        .all(|record| record.unwrap().len() == columns.len()));
+}
+
+#[test]
+fn test_output_tsv_files() {
+    compare_report(
+        WccRunner::new(),
+        |wcc_runner, path| wcc_runner.tsv_path(path),
+        GrcovFile::Coveralls(Path::new(COVERALLS_PATH)),
+        "output_tsv_files",
+    );
+}
+
+#[test]
+fn test_output_junit_functions() {
+    compare_report(
+        WccRunner::new().mode(Mode::Functions),
+        |wcc_runner, path| wcc_runner.junit_path(path),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_junit_functions",
+    );
+}
+
+#[test]
+fn test_output_code_climate_functions() {
+    compare_json_report(
+        WccRunner::new().mode(Mode::Functions),
+        |wcc_runner, path| wcc_runner.code_climate_path(path),
+        GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        "output_code_climate_functions",
+    );
+}
+
+#[test]
+fn test_output_sonar_functions() {
+    compare_json_report(
+        WccRunner::new().mode(Mode::Functions),
+        |wcc_runner, path| wcc_runner.sonar_issues_path(path),
+        GrcovFile::Coveralls(Path::new(COVERALLS_PATH)),
+        "output_sonar_issues_functions",
+    );
+    compare_report(
+        WccRunner::new().mode(Mode::Functions),
+        |wcc_runner, path| wcc_runner.sonar_coverage_path(path),
+        GrcovFile::Coveralls(Path::new(COVERALLS_PATH)),
+        "output_sonar_coverage_functions",
+    );
+}
+
+#[test]
+fn test_output_sonar_functions_complexities() {
+    let sonar_issues: serde_json::Value = serde_json::from_str(&run_report(
+        WccRunner::new()
+            .mode(Mode::Functions)
+            .complexities(&[Complexity::HalsteadEffort]),
+        |wcc_runner, path| wcc_runner.sonar_issues_path(path),
+        GrcovFile::Coveralls(Path::new(COVERALLS_PATH)),
+        "output_sonar_issues_functions_complexities",
+    ))
+    .unwrap();
+
+    // The effort only depends on the cyclomatic complexity of the function,
+    // even for the issues of the Halstead effort variants of the metrics.
+    let mut efforts = std::collections::HashMap::new();
+    let issues = sonar_issues["issues"].as_array().unwrap();
+    assert!(issues
+        .iter()
+        .any(|issue| issue["primaryLocation"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("halstead-effort")));
+    for issue in issues {
+        let location = issue["primaryLocation"].clone();
+        let effort = issue["effortMinutes"].as_u64().unwrap();
+        assert!(effort <= 500);
+        assert_eq!(
+            *efforts
+                .entry((location["filePath"].clone(), location["textRange"].clone()))
+                .or_insert(effort),
+            effort
+        );
+    }
+}
+
+#[test]
+fn test_output_badges() {
+    let output_dir = temp_dir().join("output_badges");
+    std::fs::create_dir_all(&output_dir).unwrap();
+    WccRunner::new()
+        .json_path(&output_dir.join(JSON_OUTPUT))
+        .badges(Complexity::Cyclomatic)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+    let wcc_badge = std::fs::read_to_string(output_dir.join("wcc.svg")).unwrap();
+    let crap_badge = std::fs::read_to_string(output_dir.join("crap.svg")).unwrap();
+
+    insta::with_settings!({
+        snapshot_path => Path::new(SNAPSHOTS_PATH),
+        prepend_module_to_snapshot => false,
+    },{
+        insta::assert_snapshot!("output_badge_wcc", wcc_badge);
+        insta::assert_snapshot!("output_badge_crap", crap_badge);
+    });
+
+    // The badges complexity metric is checked before the analysis,
+    // so the missing grcov file is never read.
+    let error = WccRunner::new()
+        .json_path(&output_dir.join(JSON_OUTPUT))
+        .badges(Complexity::Exits)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new("missing.json")),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Options(message) if message == "Badges complexity metric must be computed"
+    ));
+}
+
+#[test]
+fn test_output_html_sources() {
+    let output_dir = temp_dir().join("output_html_sources");
+    std::fs::create_dir_all(&output_dir).unwrap();
+    let output = WccRunner::new()
+        .mode(Mode::Functions)
+        .html_path(&output_dir)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+
+    let sources: Vec<_> = std::fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.starts_with("source_"))
+        .collect();
+    assert_eq!(sources.len(), output.files.len());
+
+    let index = std::fs::read_to_string(output_dir.join("index.html")).unwrap();
+    for source in &sources {
+        assert!(index.contains(source.as_str()));
+        let page = std::fs::read_to_string(output_dir.join(source)).unwrap();
+        assert!(page.contains("id=\"L1\""));
+        assert!(page.contains("source-function"));
+    }
+    assert!(sources.iter().any(|source| {
+        let page = std::fs::read_to_string(output_dir.join(source)).unwrap();
+        page.contains("class=\"source-covered") && page.contains("class=\"source-uncovered")
+    }));
+}
+
+#[test]
+fn test_output_html_charts() {
+    let output_dir = temp_dir().join("output_html_charts");
+    std::fs::create_dir_all(&output_dir).unwrap();
+    let output = WccRunner::new()
+        .mode(Mode::Functions)
+        .html_path(&output_dir)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+
+    let index = std::fs::read_to_string(output_dir.join("index.html")).unwrap();
+    // Each file is drawn once for each complexity and metric.
+    assert_eq!(
+        index.matches("class=\"treemap-file\"").count(),
+        4 * output.files.len()
+    );
+    let functions: usize = output
+        .files
+        .iter()
+        .filter_map(|file| file.functions.as_ref())
+        .map(Vec::len)
+        .sum();
+    let scatter = index
+        .lines()
+        .find(|line| line.contains("const scatterData"))
+        .unwrap();
+    assert_eq!(scatter.matches("\"coverage\":").count(), functions);
+    assert!(scatter.contains("\"wccComplexity\":15.0"));
+}
+
+#[test]
+fn test_output_html_table() {
+    let output_dir = temp_dir().join("output_html_table");
+    std::fs::create_dir_all(&output_dir).unwrap();
+    let output = WccRunner::new()
+        .mode(Mode::Functions)
+        .html_path(&output_dir)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+
+    // Rows are embedded as json instead of being rendered in html.
+    let table_rows = |page: &str| -> Vec<serde_json::Value> {
+        let html = std::fs::read_to_string(output_dir.join(page)).unwrap();
+        let (_, rows) = html.split_once("new WccTable(").unwrap();
+        let (rows, _) = rows.split_once(", nameCell").unwrap();
+        serde_json::from_str(rows).unwrap()
+    };
+
+    let files = table_rows("index.html");
+    assert_eq!(files.len(), output.files.len());
+    for (row, file) in files.iter().zip(&output.files) {
+        assert_eq!(row["name"], file.name.as_str());
+        assert_eq!(row["metrics"]["coverage"], file.metrics.coverage);
+        assert!(row["source"].as_str().unwrap().starts_with("source_"));
+    }
+
+    let (file_number, file) = output
+        .files
+        .iter()
+        .enumerate()
+        .find(|(_, file)| file.functions.is_some())
+        .unwrap();
+    let functions = table_rows(&format!("file_{}.html", file_number + 1));
+    assert_eq!(functions.len(), file.functions.as_ref().unwrap().len());
+    assert!(functions[0]["source"].as_str().unwrap().contains("#L"));
+}
+
+#[test]
+fn test_output_html_templates() {
+    let output_dir = temp_dir().join("output_html_templates");
+    let templates_dir = output_dir.join("templates");
+    std::fs::create_dir_all(&templates_dir).unwrap();
+    std::fs::write(
+        templates_dir.join("navbar.html"),
+        "<nav>ACME {{ wcc_output.files|length }} files</nav>",
+    )
+    .unwrap();
+    let output = WccRunner::new()
+        .mode(Mode::Functions)
+        .html_path(&output_dir)
+        .html_templates(&templates_dir)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+
+    let navbar = format!("<nav>ACME {} files</nav>", output.files.len());
+    for page in ["index.html", "file_2.html", "source_1.html"] {
+        let html = std::fs::read_to_string(output_dir.join(page)).unwrap();
+        assert!(
+            html.contains(&navbar),
+            "{page} does not use the custom navbar"
+        );
+    }
+
+    let error = WccRunner::new()
+        .html_path(&output_dir)
+        .html_templates(&templates_dir.join("navbar.html"))
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Html templates path must be a directory");
+}
+
+#[test]
+fn test_output_html_single() {
+    let output_dir = temp_dir().join("output_html_single");
+    let _ = std::fs::remove_dir_all(&output_dir);
+    std::fs::create_dir_all(&output_dir).unwrap();
+    let output = WccRunner::new()
+        .mode(Mode::Functions)
+        .html_path(&output_dir)
+        .html_single(true)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+
+    let written: Vec<_> = std::fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(written, ["index.html"]);
+
+    let html = std::fs::read_to_string(output_dir.join("index.html")).unwrap();
+    // The vendored assets are inlined, so nothing is loaded from the CDNs.
+    #[cfg(feature = "offline-html")]
+    assert!(!html.contains("cdn.jsdelivr.net"));
+    // The pages are embedded as json, without closing the script.
+    assert_eq!(html.matches("</script>").count(), 1);
+    let pages = html
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("const pages = "))
+        .and_then(|pages| pages.strip_suffix(';'))
+        .unwrap();
+    let pages: serde_json::Map<String, serde_json::Value> = serde_json::from_str(pages).unwrap();
+    assert!(pages["index.html"]
+        .as_str()
+        .unwrap()
+        .contains("const wccTable = new WccTable("));
+    assert!(pages.contains_key("file_2.html"));
+    assert!(pages.contains_key("source_1.html"));
+    let details = output
+        .files
+        .iter()
+        .filter(|file| file.functions.is_some())
+        .count();
+    assert!(pages.len() > details);
+}
+
+#[test]
+fn test_output_history() {
+    let output_dir = temp_dir().join("output_history");
+    std::fs::create_dir_all(&output_dir).unwrap();
+    let history_path = output_dir.join("history.jsonl");
+    let _ = std::fs::remove_file(&history_path);
+
+    WccRunner::new()
+        .history_path(&history_path)
+        .history_commit("0123456789abcdef")
+        .history_label("baseline")
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+    let output = WccRunner::new()
+        .mode(Mode::Functions)
+        .html_path(&output_dir)
+        .history_path(&history_path)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+
+    let history = History::from_path(&history_path).unwrap();
+    assert_eq!(history.entries.len(), 2);
+    assert_eq!(history.entries[1].files.len(), output.files.len());
+    assert_eq!(
+        history.entries[1].project.total.coverage,
+        output.project.total.coverage
+    );
+
+    let base = history.find("baseline").unwrap();
+    assert_eq!(
+        history.find("0123").map(|entry| entry.timestamp),
+        Some(base.timestamp)
+    );
+    assert_eq!(
+        history.find("2").map(|entry| entry.mode),
+        Some(Mode::Functions)
+    );
+    assert!(history.find("missing").is_none());
+
+    let runs = history.format_runs(Complexity::Cyclomatic);
+    assert_eq!(runs.lines().count(), 3);
+    assert!(runs.lines().nth(1).unwrap().contains("0123456   baseline"));
+
+    let comparison =
+        History::format_comparison(base, &history.entries[1], Complexity::Cognitive).unwrap();
+    assert!(comparison.starts_with("Comparing baseline with "));
+    assert!(comparison.contains("Coverage"));
+    assert!(!comparison.contains("Added files"));
+    let error = History::format_comparison(base, base, Complexity::HalsteadEffort).unwrap_err();
+    assert!(matches!(error, Error::History(_)));
+    // Runs lacking the complexity metric are listed too.
+    let runs = history.format_runs(Complexity::HalsteadEffort);
+    assert_eq!(runs.lines().count(), 3);
+    assert!(runs
+        .lines()
+        .nth(1)
+        .unwrap()
+        .contains("%         -         -         -"));
+
+    let html = std::fs::read_to_string(output_dir.join("index.html")).unwrap();
+    assert!(html.contains(r#"<canvas id="trend"></canvas>"#));
+    assert!(html.contains(r#"const trendData = [{"name":"baseline""#));
+
+    let error = WccRunner::new()
+        .history_label("baseline")
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Options(message) if message == "History commit and label need a history path"
+    ));
+
+    // Runs stored with a different major schema version are rejected.
+    let history = std::fs::read_to_string(&history_path).unwrap();
+    let (base, head) = history.split_once('\n').unwrap();
+    let head = head.replacen(
+        &format!(r#""schemaVersion":"{SCHEMA_VERSION}""#),
+        r#""schemaVersion":"1.2""#,
+        1,
+    );
+    std::fs::write(&history_path, format!("{base}\n{head}")).unwrap();
+    let error = History::from_path(&history_path).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::History(message)
+            if message == r#"History run at line 2 has the incompatible schema version "1.2""#
+    ));
+}
+
+#[test]
+fn test_output_json_report() {
+    let output_dir = temp_dir().join("output_json_report");
+    std::fs::create_dir_all(&output_dir).unwrap();
+    let json_path = output_dir.join(JSON_OUTPUT);
+    let output = WccRunner::new()
+        .mode(Mode::Functions)
+        .complexities(&[Complexity::Exits])
+        .json_path(&json_path)
+        .run(
+            Path::new(PROJECT_PATH),
+            GrcovFile::Covdir(Path::new(COVDIR_PATH)),
+        )
+        .unwrap();
+
+    let report = WccReport::from_path(&json_path).unwrap();
+    assert_eq!(report.schema_version, SCHEMA_VERSION);
+    assert_eq!(report.mode, Mode::Functions);
+    assert_eq!(report.files.len(), output.files.len());
+    assert_eq!(
+        report
+            .project_metrics
+            .total
+            .complexities
+            .keys()
+            .collect::<Vec<_>>(),
+        [&Complexity::Exits]
+    );
+    assert_eq!(
+        report.complex_files_cognitive.len(),
+        output
+            .files
+            .iter()
+            .filter(|file| file.metrics.cognitive.is_complex)
+            .count()
+    );
+
+    // Fields added by newer minor versions are ignored.
+    let mut json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    json["futureField"] = 1.into();
+    json["projectMetrics"]["total"]["futureField"] = 1.into();
+    json["files"][0]["metrics"]["futureField"] = 1.into();
+    std::fs::write(&json_path, json.to_string()).unwrap();
+    let report = WccReport::from_path(&json_path).unwrap();
+    assert_eq!(report.files.len(), output.files.len());
+
+    // Reports with a different major version are rejected.
+    json["schemaVersion"] = "0.1".into();
+    std::fs::write(&json_path, json.to_string()).unwrap();
+    assert!(matches!(
+        WccReport::from_path(&json_path),
+        Err(Error::SchemaVersion(version)) if version == "0.1"
+    ));
+}
+
+#[test]
+fn test_output_json_schema() {
+    let schema = serde_json::to_string_pretty(&WccReport::json_schema()).unwrap() + "\n";
+    if std::env::var_os("WCC_UPDATE_SCHEMA").is_some() {
+        std::fs::write(JSON_SCHEMA_PATH, &schema).unwrap();
+    }
+
+    assert_eq!(
+        std::fs::read_to_string(JSON_SCHEMA_PATH).unwrap(),
+        schema,
+        "The json schema is outdated, run the tests with WCC_UPDATE_SCHEMA=1 to update it"
+    );
+}
+
 fn compare(grcov_file: GrcovFile<&Path>, mode: Mode, snapshot_name: &str) {
+    compare_runner(WccRunner::new().mode(mode), grcov_file, snapshot_name);
+}
+
+fn compare_runner(wcc_runner: WccRunner, grcov_file: GrcovFile<&Path>, snapshot_name: &str) {
     let output_dir = temp_dir();
 
-    let output = WccRunner::new()
-        .mode(mode)
+    let output = wcc_runner
         .json_path(&output_dir.join(JSON_OUTPUT))
         .html_path(&output_dir)
         .run(Path::new(PROJECT_PATH), grcov_file)
@@ -59,6 +782,59 @@ fn compare(grcov_file: GrcovFile<&Path>, mode: Mode, snapshot_name: &str) {
         snapshot_path => Path::new(SNAPSHOTS_PATH),
         prepend_module_to_snapshot => false,
     },{
-        insta::assert_yaml_snapshot!(snapshot_name, output, { ".files" => sorted_redaction(), ".ignored_files" => sorted_redaction(), ".files.*.functions" => sorted_redaction() });
+        insta::assert_yaml_snapshot!(snapshot_name, output, { ".files" => sorted_redaction(), ".ignored_files" => sorted_redaction(), ".files.*.functions" => sorted_redaction(), ".project.total.*.complexity" => rounded_redaction(6), ".project.total.complexities.*.complexity" => rounded_redaction(6) });
+    });
+}
+
+// Runs the analysis writing a single report, named as its snapshot,
+// into the temporary directory, and reads it.
+fn run_report(
+    wcc_runner: WccRunner,
+    report: for<'a> fn(WccRunner<'a>, &'a Path) -> WccRunner<'a>,
+    grcov_file: GrcovFile<&Path>,
+    snapshot_name: &str,
+) -> String {
+    let report_path = temp_dir().join(snapshot_name);
+    report(wcc_runner, &report_path)
+        .run(Path::new(PROJECT_PATH), grcov_file)
+        .unwrap();
+
+    std::fs::read_to_string(report_path).unwrap()
+}
+
+fn compare_report(
+    wcc_runner: WccRunner,
+    report: for<'a> fn(WccRunner<'a>, &'a Path) -> WccRunner<'a>,
+    grcov_file: GrcovFile<&Path>,
+    snapshot_name: &str,
+) -> String {
+    let output = run_report(wcc_runner, report, grcov_file, snapshot_name);
+
+    insta::with_settings!({
+        snapshot_path => Path::new(SNAPSHOTS_PATH),
+        prepend_module_to_snapshot => false,
+    },{
+        insta::assert_snapshot!(snapshot_name, output);
     });
+
+    output
+}
+
+fn compare_json_report(
+    wcc_runner: WccRunner,
+    report: for<'a> fn(WccRunner<'a>, &'a Path) -> WccRunner<'a>,
+    grcov_file: GrcovFile<&Path>,
+    snapshot_name: &str,
+) -> serde_json::Value {
+    let output: serde_json::Value =
+        serde_json::from_str(&run_report(wcc_runner, report, grcov_file, snapshot_name)).unwrap();
+
+    insta::with_settings!({
+        snapshot_path => Path::new(SNAPSHOTS_PATH),
+        prepend_module_to_snapshot => false,
+    },{
+        insta::assert_yaml_snapshot!(snapshot_name, output, { ".runs[].tool.driver.version" => "[version]" });
+    });
+
+    output
 }